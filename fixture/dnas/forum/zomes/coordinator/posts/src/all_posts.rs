@@ -1,7 +1,85 @@
 use hdk::prelude::*;
 use posts_integrity::*;
+use crate::moderation::{apply_listing_options, ListingOptions};
+/// Pass `null` to list every post, hidden or not.
 #[hdk_extern]
-pub fn get_all_posts(_: ()) -> ExternResult<Vec<Link>> {
-    let path = Path::from("all_posts");
-    get_links(GetLinksInputBuilder::try_new(path.path_entry_hash()?, LinkTypes::AllPosts)?.build())
+pub fn get_all_posts(options: Option<ListingOptions>) -> ExternResult<Vec<Link>> {
+    let path = Path::from(ALL_POSTS_ANCHOR);
+    let links = get_links(
+        GetLinksInputBuilder::try_new(path.path_entry_hash()?, LinkTypes::AllPosts)?.build(),
+    )?;
+    let links = remove_deleted_posts(links)?;
+    apply_listing_options(links, &options.unwrap_or_default())
+}
+/// Only post authors can delete their AllPosts links, so the links to posts deleted by a
+/// moderator stay and are left out here.
+fn remove_deleted_posts(links: Vec<Link>) -> ExternResult<Vec<Link>> {
+    let links: Vec<(Link, ActionHash)> = links
+        .into_iter()
+        .filter_map(|link| {
+            let post_hash = link.target.clone().into_action_hash()?;
+            Some((link, post_hash))
+        })
+        .collect();
+    let input: Vec<GetInput> = links
+        .iter()
+        .map(|(_, post_hash)| GetInput::new(post_hash.clone().into(), GetOptions::default()))
+        .collect();
+    let details = HDK.with(|hdk| hdk.borrow().get_details(input))?;
+    let latest_acceptable_timestamp = crate::latest_acceptable_timestamp()?;
+    Ok(links
+        .into_iter()
+        .zip(details)
+        .filter(|(_, details)| match details {
+            Some(Details::Record(details)) => !details
+                .deletes
+                .iter()
+                .any(|delete| delete.action().timestamp() <= latest_acceptable_timestamp),
+            _ => true,
+        })
+        .map(|((link, _), _)| link)
+        .collect())
+}
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RankedPost {
+    pub post_hash: ActionHash,
+    pub score: crate::vote::Score,
+    /// What the listing is sorted by, highest first.
+    pub rank: f64,
+}
+/// How fast posts sink in `get_hot_posts` as they age.
+const HOT_GRAVITY: f64 = 1.8;
+/// All posts sorted by their net score.
+#[hdk_extern]
+pub fn get_top_posts(_: ()) -> ExternResult<Vec<RankedPost>> {
+    rank_posts(|score, _| score.net() as f64)
+}
+/// All posts sorted by their net score decayed by the time since they were posted.
+#[hdk_extern]
+pub fn get_hot_posts(_: ()) -> ExternResult<Vec<RankedPost>> {
+    let now = sys_time()?;
+    rank_posts(|score, posted_at| {
+        let age_hours = (now.as_micros() - posted_at.as_micros()).max(0) as f64 / 3_600_000_000.0;
+        score.net() as f64 / (age_hours + 2.0).powf(HOT_GRAVITY)
+    })
+}
+fn rank_posts(
+    rank: impl Fn(&crate::vote::Score, Timestamp) -> f64,
+) -> ExternResult<Vec<RankedPost>> {
+    let mut ranked_posts = get_all_posts(None)?
+        .into_iter()
+        .map(|link| {
+            let post_hash = link.target.into_action_hash().ok_or(wasm_error!(
+                WasmErrorInner::Guest(String::from("No action hash associated with link"))
+            ))?;
+            let score = crate::vote::get_score(post_hash.clone())?;
+            Ok(RankedPost {
+                rank: rank(&score, link.timestamp),
+                post_hash,
+                score,
+            })
+        })
+        .collect::<ExternResult<Vec<RankedPost>>>()?;
+    ranked_posts.sort_by(|a, b| b.rank.total_cmp(&a.rank));
+    Ok(ranked_posts)
 }
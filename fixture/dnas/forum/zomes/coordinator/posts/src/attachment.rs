@@ -0,0 +1,87 @@
+use hdk::prelude::*;
+use posts_integrity::*;
+/// Stores one chunk of a file, to be referenced by `create_file_metadata` once all are uploaded.
+#[hdk_extern]
+pub fn upload_file_chunk(file_chunk: FileChunk) -> ExternResult<EntryHash> {
+    create_entry(&EntryTypes::FileChunk(file_chunk.clone()))?;
+    hash_entry(&file_chunk)
+}
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CreateFileMetadataInput {
+    pub name: String,
+    pub mime_type: String,
+    /// The hashes returned by `upload_file_chunk`, in order.
+    pub chunks: Vec<EntryHash>,
+}
+/// Describes a file made of already uploaded chunks, computing its size and content hash.
+#[hdk_extern]
+pub fn create_file_metadata(input: CreateFileMetadataInput) -> ExternResult<Record> {
+    let content = get_file_content(&input.chunks)?;
+    let file_metadata = FileMetadata {
+        name: input.name,
+        mime_type: input.mime_type,
+        size: content.len() as u64,
+        chunks: input.chunks,
+        content_hash: hash_blake2b(content, CONTENT_HASH_LENGTH)?,
+    };
+    let file_metadata_hash = create_entry(&EntryTypes::FileMetadata(file_metadata))?;
+    get(file_metadata_hash, GetOptions::default())?.ok_or(wasm_error!(WasmErrorInner::Guest(
+        String::from("Could not find the newly created FileMetadata")
+    )))
+}
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AttachFileToPostInput {
+    pub post_hash: ActionHash,
+    pub file_metadata_hash: ActionHash,
+}
+#[hdk_extern]
+pub fn attach_file_to_post(input: AttachFileToPostInput) -> ExternResult<ActionHash> {
+    create_link(
+        input.post_hash,
+        input.file_metadata_hash,
+        LinkTypes::PostToAttachments,
+        (),
+    )
+}
+#[hdk_extern]
+pub fn get_attachments_for_post(post_hash: ActionHash) -> ExternResult<Vec<Link>> {
+    get_links(GetLinksInputBuilder::try_new(post_hash, LinkTypes::PostToAttachments)?.build())
+}
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DownloadedFile {
+    pub metadata: FileMetadata,
+    #[serde(with = "serde_bytes")]
+    pub content: Vec<u8>,
+}
+/// Reassembles a file from its chunks, failing if they don't add up to its size and hash.
+#[hdk_extern]
+pub fn download_file(file_metadata_hash: ActionHash) -> ExternResult<DownloadedFile> {
+    let record = get(file_metadata_hash, GetOptions::default())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest(String::from("FileMetadata not found"))
+    ))?;
+    let metadata = FileMetadata::try_from(record)?;
+    let content = get_file_content(&metadata.chunks)?;
+    if content.len() as u64 != metadata.size
+        || hash_blake2b(content.clone(), CONTENT_HASH_LENGTH)?.ne(&metadata.content_hash)
+    {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "The downloaded content does not match the file's size and hash"
+        ))));
+    }
+    Ok(DownloadedFile { metadata, content })
+}
+fn get_file_content(chunks: &[EntryHash]) -> ExternResult<Vec<u8>> {
+    let input = chunks
+        .iter()
+        .map(|chunk_hash| GetInput::new(chunk_hash.clone().into(), GetOptions::default()))
+        .collect();
+    let records = HDK.with(|hdk| hdk.borrow().get(input))?;
+    let mut content = Vec::new();
+    for record in records {
+        let record = record.ok_or(wasm_error!(WasmErrorInner::Guest(String::from(
+            "File chunk not found"
+        ))))?;
+        content.extend(FileChunk::try_from(record)?.bytes);
+    }
+    Ok(content)
+}
@@ -0,0 +1,57 @@
+use hdk::prelude::*;
+use std::collections::BTreeMap;
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PostWithAuthor {
+    pub post_hash: ActionHash,
+    pub author: AgentPubKey,
+    /// The author's nickname, `None` if they haven't created a profile.
+    pub nickname: Option<String>,
+}
+/// Like `get_all_posts`, with each post's author and their nickname.
+#[hdk_extern]
+pub fn get_all_posts_with_authors(_: ()) -> ExternResult<Vec<PostWithAuthor>> {
+    let links = crate::all_posts::get_all_posts(None)?;
+    let nicknames = get_nicknames(links.iter().map(|link| link.author.clone()))?;
+    links
+        .into_iter()
+        .map(|link| {
+            let post_hash = link.target.into_action_hash().ok_or(wasm_error!(
+                WasmErrorInner::Guest(String::from("No action hash associated with link"))
+            ))?;
+            Ok(PostWithAuthor {
+                post_hash,
+                nickname: nicknames.get(&link.author).cloned().flatten(),
+                author: link.author,
+            })
+        })
+        .collect()
+}
+/// Looks up the nicknames of the given agents in the profiles zome, once per agent.
+pub(crate) fn get_nicknames(
+    agents: impl IntoIterator<Item = AgentPubKey>,
+) -> ExternResult<BTreeMap<AgentPubKey, Option<String>>> {
+    let mut nicknames = BTreeMap::new();
+    for agent in agents {
+        if nicknames.contains_key(&agent) {
+            continue;
+        }
+        let nickname = get_nickname(agent.clone())?;
+        nicknames.insert(agent, nickname);
+    }
+    Ok(nicknames)
+}
+fn get_nickname(agent: AgentPubKey) -> ExternResult<Option<String>> {
+    let response = call(
+        CallTargetCell::Local,
+        ZomeName::from("profiles"),
+        FunctionName::from("get_agent_nickname"),
+        None,
+        agent,
+    )?;
+    match response {
+        ZomeCallResponse::Ok(result) => result.decode().map_err(|e| wasm_error!(e)),
+        _ => Err(wasm_error!(WasmErrorInner::Guest(format!(
+            "Could not get the nickname from the profiles zome: {response:?}"
+        )))),
+    }
+}
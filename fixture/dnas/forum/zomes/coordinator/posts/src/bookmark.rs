@@ -0,0 +1,44 @@
+use hdk::prelude::*;
+use std::collections::BTreeSet;
+use posts_integrity::*;
+/// Bookmarks the bookmark's target for the calling agent, replacing any previous bookmark on it.
+#[hdk_extern]
+pub fn add_bookmark(bookmark: Bookmark) -> ExternResult<Record> {
+    remove_bookmark(bookmark.target_hash.clone())?;
+    let bookmark_hash = create_entry(&EntryTypes::Bookmark(bookmark))?;
+    let record = get(bookmark_hash, GetOptions::default())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest(String::from("Could not find the newly created Bookmark"))
+    ))?;
+    Ok(record)
+}
+#[hdk_extern]
+pub fn remove_bookmark(target_hash: ActionHash) -> ExternResult<()> {
+    for record in get_my_bookmarks(())? {
+        let bookmark = Bookmark::try_from(record.clone())?;
+        if bookmark.target_hash.eq(&target_hash) {
+            delete_entry(record.action_address().clone())?;
+        }
+    }
+    Ok(())
+}
+/// The calling agent's bookmarks, read from their own source chain, newest first.
+#[hdk_extern]
+pub fn get_my_bookmarks(_: ()) -> ExternResult<Vec<Record>> {
+    let deletes = query(ChainQueryFilter::new().action_type(ActionType::Delete))?;
+    let deleted_hashes: BTreeSet<ActionHash> = deletes
+        .into_iter()
+        .filter_map(|record| match record.action() {
+            Action::Delete(delete) => Some(delete.deletes_address.clone()),
+            _ => None,
+        })
+        .collect();
+    let bookmark_entry_type: EntryType = UnitEntryTypes::Bookmark.try_into()?;
+    let bookmarks = query(
+        ChainQueryFilter::new().entry_type(bookmark_entry_type).include_entries(true),
+    )?;
+    Ok(bookmarks
+        .into_iter()
+        .rev()
+        .filter(|record| !deleted_hashes.contains(record.action_address()))
+        .collect())
+}
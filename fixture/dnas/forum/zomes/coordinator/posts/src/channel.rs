@@ -0,0 +1,159 @@
+use hdk::prelude::*;
+use posts_integrity::*;
+#[hdk_extern]
+pub fn create_channel(channel: Channel) -> ExternResult<Record> {
+    let channel_hash = create_entry(&EntryTypes::Channel(channel))?;
+    let record = get(channel_hash.clone(), GetOptions::default())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest(String::from("Could not find the newly created Channel"))
+    ))?;
+    let path = Path::from(ALL_CHANNELS_ANCHOR);
+    create_link(path.path_entry_hash()?, channel_hash, LinkTypes::AllChannels, ())?;
+    Ok(record)
+}
+#[hdk_extern]
+pub fn get_all_channels(_: ()) -> ExternResult<Vec<Link>> {
+    let path = Path::from(ALL_CHANNELS_ANCHOR);
+    get_links(GetLinksInputBuilder::try_new(path.path_entry_hash()?, LinkTypes::AllChannels)?.build())
+}
+#[hdk_extern]
+pub fn get_posts_in_channel(channel_hash: ActionHash) -> ExternResult<Vec<Link>> {
+    get_links(GetLinksInputBuilder::try_new(channel_hash, LinkTypes::ChannelToPosts)?.build())
+}
+/// Lists the post in the channel, mirroring the listing from the post so that it can be found
+/// without going through every channel.
+pub(crate) fn add_channel_link(
+    original_post_hash: &ActionHash,
+    channel_hash: &ActionHash,
+) -> ExternResult<()> {
+    add_channel_link_with_tag(original_post_hash, channel_hash, LinkTag::new(vec![]))
+}
+fn add_channel_link_with_tag(
+    original_post_hash: &ActionHash,
+    channel_hash: &ActionHash,
+    tag: LinkTag,
+) -> ExternResult<()> {
+    let channel_link_hash = create_link(
+        channel_hash.clone(),
+        original_post_hash.clone(),
+        LinkTypes::ChannelToPosts,
+        tag,
+    )?;
+    create_link(
+        original_post_hash.clone(),
+        channel_hash.clone(),
+        LinkTypes::PostToChannel,
+        LinkTag::new(channel_link_hash.get_raw_39().to_vec()),
+    )?;
+    Ok(())
+}
+/// The PostToChannel links of the post, one for each channel it is listed in.
+fn get_channel_links_for_post(original_post_hash: &ActionHash) -> ExternResult<Vec<Link>> {
+    get_links(
+        GetLinksInputBuilder::try_new(original_post_hash.clone(), LinkTypes::PostToChannel)?
+            .build(),
+    )
+}
+/// Deletes the listing of the post in a channel, along with its mirror.
+fn remove_channel_link(post_to_channel_link: Link) -> ExternResult<()> {
+    if let Some(channel_link_hash) = channel_link_hash_from_link_tag(&post_to_channel_link.tag) {
+        delete_link(channel_link_hash)?;
+    }
+    delete_link(post_to_channel_link.create_link_hash)?;
+    Ok(())
+}
+/// Removes the post from every channel it is listed in.
+pub(crate) fn remove_channel_links(original_post_hash: &ActionHash) -> ExternResult<()> {
+    for link in get_channel_links_for_post(original_post_hash)? {
+        remove_channel_link(link)?;
+    }
+    Ok(())
+}
+/// Lists the restored copy of a post in the channel the original was last listed in, which
+/// can differ from its `channel_hash` when a moderator moved it.
+pub(crate) fn add_channel_link_for_restored_copy(
+    original_post_hash: &ActionHash,
+    restored_post_hash: &ActionHash,
+    restored_from_hash: ActionHash,
+    post: &Post,
+) -> ExternResult<()> {
+    let details = get_link_details(
+        original_post_hash.clone(),
+        LinkTypes::PostToChannel,
+        None,
+        GetOptions::default(),
+    )?;
+    let last_listing = details
+        .into_inner()
+        .into_iter()
+        .filter_map(|(create_link, _deletes)| match create_link.action() {
+            Action::CreateLink(create_link) => Some(create_link.clone()),
+            _ => None,
+        })
+        .max_by_key(|create_link| create_link.timestamp);
+    let Some(last_listing) = last_listing else {
+        if let Some(channel_hash) = &post.channel_hash {
+            add_channel_link(restored_post_hash, channel_hash)?;
+        }
+        return Ok(());
+    };
+    let (Some(channel_hash), Some(channel_link_hash)) = (
+        last_listing.target_address.into_action_hash(),
+        channel_link_hash_from_link_tag(&last_listing.tag),
+    ) else {
+        return Ok(());
+    };
+    let listing = RestoredChannelListing {
+        restored_from_hash,
+        channel_link_hash,
+    };
+    let tag = LinkTag::new(
+        SerializedBytes::try_from(listing)
+            .map_err(|e| wasm_error!(e))?
+            .bytes()
+            .clone(),
+    );
+    add_channel_link_with_tag(restored_post_hash, &channel_hash, tag)
+}
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MovePostToChannelInput {
+    pub original_post_hash: ActionHash,
+    pub to_channel_hash: ActionHash,
+}
+/// Lists the post in the given channel only, removing it from the channels it is currently
+/// listed in. The caller must moderate both the destination and the source channels.
+#[hdk_extern]
+pub fn move_post_to_channel(input: MovePostToChannelInput) -> ExternResult<()> {
+    let config = forum_config()?;
+    let my_pub_key = agent_info()?.agent_initial_pubkey;
+    let channel_record = get(input.to_channel_hash.clone(), GetOptions::default())?.ok_or(
+        wasm_error!(WasmErrorInner::Guest(String::from("Channel not found"))),
+    )?;
+    if !is_channel_moderator(&channel_record, &my_pub_key, &config)? {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "Only channel moderators can move posts into a channel"
+        ))));
+    }
+    let mut already_listed = false;
+    for link in get_channel_links_for_post(&input.original_post_hash)? {
+        let Some(from_channel_hash) = link.target.clone().into_action_hash() else {
+            continue;
+        };
+        if from_channel_hash.eq(&input.to_channel_hash) {
+            already_listed = true;
+            continue;
+        }
+        let from_channel_record = get(from_channel_hash, GetOptions::default())?.ok_or(
+            wasm_error!(WasmErrorInner::Guest(String::from("Channel not found"))),
+        )?;
+        if !is_channel_moderator(&from_channel_record, &my_pub_key, &config)? {
+            return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+                "Only channel moderators can move posts out of a channel"
+            ))));
+        }
+        remove_channel_link(link)?;
+    }
+    if already_listed {
+        return Ok(());
+    }
+    add_channel_link(&input.original_post_hash, &input.to_channel_hash)
+}
@@ -1,5 +1,7 @@
 use hdk::prelude::*;
+use std::collections::{BTreeMap, BTreeSet};
 use posts_integrity::*;
+use crate::moderation::{apply_listing_options, ListingOptions};
 #[hdk_extern]
 pub fn create_comment(comment: Comment) -> ExternResult<Record> {
     let comment_hash = create_entry(&EntryTypes::Comment(comment.clone()))?;
@@ -9,6 +11,25 @@ pub fn create_comment(comment: Comment) -> ExternResult<Record> {
         LinkTypes::PostToComments,
         (),
     )?;
+    if let Some(parent_comment_hash) = comment.parent_comment_hash.clone() {
+        create_link(
+            parent_comment_hash.clone(),
+            comment_hash.clone(),
+            LinkTypes::CommentToReplies,
+            (),
+        )?;
+        crate::restore::link_to_restored_copies(
+            parent_comment_hash,
+            comment_hash.clone(),
+            LinkTypes::CommentToReplies,
+        )?;
+    }
+    crate::restore::link_to_restored_copies(
+        comment.post_hash.clone(),
+        comment_hash.clone(),
+        LinkTypes::PostToComments,
+    )?;
+    crate::mention::add_mention_links(&comment_hash, &comment.comment)?;
     let record = get(comment_hash.clone(), GetOptions::default())?.ok_or(wasm_error!(
         WasmErrorInner::Guest(String::from("Could not find the newly created Comment"))
     ))?;
@@ -56,6 +77,30 @@ pub fn delete_comment(original_comment_hash: ActionHash) -> ExternResult<ActionH
             }
         }
     }
+    crate::restore::unlink_from_restored_copies(
+        comment.post_hash.clone(),
+        &original_comment_hash,
+        LinkTypes::PostToComments,
+    )?;
+    if let Some(parent_comment_hash) = comment.parent_comment_hash {
+        crate::restore::unlink_from_restored_copies(
+            parent_comment_hash.clone(),
+            &original_comment_hash,
+            LinkTypes::CommentToReplies,
+        )?;
+        let links = get_links(
+            GetLinksInputBuilder::try_new(parent_comment_hash, LinkTypes::CommentToReplies)?
+                .build(),
+        )?;
+        for link in links {
+            if let Some(action_hash) = link.target.into_action_hash() {
+                if action_hash.eq(&original_comment_hash) {
+                    delete_link(link.create_link_hash)?;
+                }
+            }
+        }
+    }
+    crate::mention::remove_mention_links(&original_comment_hash, &comment.comment)?;
     delete_entry(original_comment_hash)
 }
 #[hdk_extern]
@@ -72,24 +117,51 @@ pub fn get_all_deletes_for_comment(
         Details::Record(record_details) => Ok(Some(record_details.deletes)),
     }
 }
+/// The oldest delete of the comment, along with the copy it was restored as, if any.
 #[hdk_extern]
 pub fn get_oldest_delete_for_comment(
     original_comment_hash: ActionHash,
-) -> ExternResult<Option<SignedActionHashed>> {
-    let Some(mut deletes) = get_all_deletes_for_comment(original_comment_hash)? else {
+) -> ExternResult<Option<crate::restore::DeletionStatus>> {
+    let Some(mut deletes) = get_all_deletes_for_comment(original_comment_hash.clone())? else {
         return Ok(None);
     };
+    let latest_acceptable_timestamp = crate::latest_acceptable_timestamp()?;
+    deletes.retain(|delete| delete.action().timestamp() <= latest_acceptable_timestamp);
     deletes.sort_by(|delete_a, delete_b| {
         delete_a
             .action()
             .timestamp()
             .cmp(&delete_b.action().timestamp())
     });
-    Ok(deletes.first().cloned())
+    let Some(delete) = deletes.first().cloned() else {
+        return Ok(None);
+    };
+    Ok(Some(crate::restore::DeletionStatus {
+        delete,
+        restored_as: crate::restore::get_restored_as(original_comment_hash)?,
+    }))
+}
+/// Either a bare post hash, listing every comment, or the post hash with listing options.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(untagged)]
+pub enum GetCommentsForPostInput {
+    PostHash(ActionHash),
+    WithOptions {
+        post_hash: ActionHash,
+        #[serde(flatten)]
+        options: ListingOptions,
+    },
 }
 #[hdk_extern]
-pub fn get_comments_for_post(post_hash: ActionHash) -> ExternResult<Vec<Link>> {
-    get_links(GetLinksInputBuilder::try_new(post_hash, LinkTypes::PostToComments)?.build())
+pub fn get_comments_for_post(input: GetCommentsForPostInput) -> ExternResult<Vec<Link>> {
+    let (post_hash, options) = match input {
+        GetCommentsForPostInput::PostHash(post_hash) => (post_hash, ListingOptions::default()),
+        GetCommentsForPostInput::WithOptions { post_hash, options } => (post_hash, options),
+    };
+    let links = get_links(
+        GetLinksInputBuilder::try_new(post_hash, LinkTypes::PostToComments)?.build(),
+    )?;
+    apply_listing_options(links, &options)
 }
 #[hdk_extern]
 pub fn get_deleted_comments_for_post(
@@ -104,6 +176,88 @@ pub fn get_deleted_comments_for_post(
     Ok(details
         .into_inner()
         .into_iter()
-        .filter(|(_link, deletes)| deletes.len() > 0)
+        .filter(|(_link, deletes)| !deletes.is_empty())
         .collect())
 }
+#[hdk_extern]
+pub fn get_replies_for_comment(comment_hash: ActionHash) -> ExternResult<Vec<Link>> {
+    get_links(GetLinksInputBuilder::try_new(comment_hash, LinkTypes::CommentToReplies)?.build())
+}
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CommentNode {
+    pub record: Record,
+    pub replies: Vec<CommentNode>,
+}
+/// Returns the comments of the given post arranged as a tree of replies, oldest first.
+///
+/// Replies whose parent comment is no longer reachable, and was not restored, are shown at the
+/// top level.
+#[hdk_extern]
+pub fn get_comment_tree(post_hash: ActionHash) -> ExternResult<Vec<CommentNode>> {
+    let links = get_comments_for_post(GetCommentsForPostInput::PostHash(post_hash))?;
+    let get_input: Vec<GetInput> = links
+        .into_iter()
+        .map(|link| {
+            Ok(GetInput::new(
+                link.target
+                    .into_action_hash()
+                    .ok_or(wasm_error!(WasmErrorInner::Guest(String::from(
+                        "No action hash associated with link"
+                    ))))?
+                    .into(),
+                GetOptions::default(),
+            ))
+        })
+        .collect::<ExternResult<Vec<GetInput>>>()?;
+    let mut records: Vec<Record> = HDK
+        .with(|hdk| hdk.borrow().get(get_input))?
+        .into_iter()
+        .flatten()
+        .collect();
+    records.sort_by_key(|record| record.action().timestamp());
+    let mut replies: BTreeMap<ActionHash, Vec<Record>> = BTreeMap::new();
+    let mut roots: Vec<Record> = vec![];
+    let comment_hashes: BTreeSet<ActionHash> = records
+        .iter()
+        .map(|record| record.action_address().clone())
+        .collect();
+    for record in records {
+        let comment = Comment::try_from(record.clone())?;
+        let Some(parent_comment_hash) = comment.parent_comment_hash else {
+            roots.push(record);
+            continue;
+        };
+        // Replies to a deleted comment that was restored go under its latest restored copy
+        let parent_comment_hash = if comment_hashes.contains(&parent_comment_hash) {
+            Some(parent_comment_hash)
+        } else {
+            crate::restore::get_restored_copies(parent_comment_hash)?
+                .into_iter()
+                .rev()
+                .map(|(copy_hash, _)| copy_hash)
+                .find(|copy_hash| comment_hashes.contains(copy_hash))
+        };
+        match parent_comment_hash {
+            Some(parent_comment_hash) => {
+                replies.entry(parent_comment_hash).or_default().push(record);
+            }
+            None => roots.push(record),
+        }
+    }
+    Ok(build_comment_nodes(roots, &mut replies))
+}
+fn build_comment_nodes(
+    records: Vec<Record>,
+    replies: &mut BTreeMap<ActionHash, Vec<Record>>,
+) -> Vec<CommentNode> {
+    records
+        .into_iter()
+        .map(|record| {
+            let children = replies.remove(record.action_address()).unwrap_or_default();
+            CommentNode {
+                replies: build_comment_nodes(children, replies),
+                record,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,21 @@
+use hdk::prelude::*;
+use posts_integrity::*;
+#[hdk_extern]
+pub fn create_invitation(invitee: AgentPubKey) -> ExternResult<SignedInvitation> {
+    let inviter = agent_info()?.agent_initial_pubkey;
+    if !forum_config()?.is_moderator(&inviter) {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "Only the progenitor and the moderators can invite agents to this forum"
+        ))));
+    }
+    let invitation = Invitation {
+        invitee,
+        inviter,
+        dna_hash: dna_info()?.hash,
+    };
+    let signature = sign(invitation.inviter.clone(), &invitation)?;
+    Ok(SignedInvitation {
+        invitation,
+        signature,
+    })
+}
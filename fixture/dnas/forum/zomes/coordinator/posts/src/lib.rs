@@ -1,12 +1,31 @@
 pub mod all_posts;
+pub mod attachment;
+pub mod author;
+pub mod bookmark;
+pub mod channel;
 pub mod comment;
+pub mod invitation;
+pub mod mention;
+pub mod moderation;
+pub mod pin;
+pub mod poll;
 pub mod post;
+pub mod reaction;
+pub mod restore;
+pub mod tag;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
+pub mod vote;
 use hdk::prelude::*;
 use posts_integrity::*;
 #[hdk_extern]
 pub fn init(_: ()) -> ExternResult<InitCallbackResult> {
     Ok(InitCallbackResult::Pass)
 }
+#[hdk_extern]
+pub fn get_forum_config(_: ()) -> ExternResult<ForumConfig> {
+    forum_config()
+}
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(tag = "type")]
 pub enum Signal {
@@ -23,6 +42,8 @@ pub enum Signal {
         original_app_entry: EntryTypes,
     },
     EntryDeleted { action: SignedActionHashed, original_app_entry: EntryTypes },
+    /// Sent once a cascading delete has finished, so UIs can drop the thread in one pass.
+    PostDeletedWithComments { report: post::CascadeDeleteReport },
 }
 #[hdk_extern(infallible)]
 pub fn post_commit(committed_actions: Vec<SignedActionHashed>) {
@@ -72,11 +93,11 @@ fn signal_action(action: SignedActionHashed) -> ExternResult<()> {
                     Ok(())
                 }
                 _ => {
-                    return Err(
+                    Err(
                         wasm_error!(
                             WasmErrorInner::Guest("Create Link should exist".to_string())
                         ),
-                    );
+                    )
                 }
             }
         }
@@ -117,6 +138,10 @@ fn signal_action(action: SignedActionHashed) -> ExternResult<()> {
         _ => Ok(()),
     }
 }
+/// Validation can't read a clock, so actions dated too far ahead of ours are ignored when reading.
+pub(crate) fn latest_acceptable_timestamp() -> ExternResult<Timestamp> {
+    Ok(sys_time()?.saturating_add(&forum_config()?.max_future_skew()))
+}
 fn get_entry_for_action(action_hash: &ActionHash) -> ExternResult<Option<EntryTypes>> {
     let record = match get_details(action_hash.clone(), GetOptions::default())? {
         Some(Details::Record(record_details)) => record_details.record,
@@ -138,11 +163,5 @@ fn get_entry_for_action(action_hash: &ActionHash) -> ExternResult<Option<EntryTy
             return Ok(None);
         }
     };
-    Ok(
-        EntryTypes::deserialize_from_type(
-            zome_index.clone(),
-            entry_index.clone(),
-            entry,
-        )?,
-    )
+    EntryTypes::deserialize_from_type(*zome_index, *entry_index, entry)
 }
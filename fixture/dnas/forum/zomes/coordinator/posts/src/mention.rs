@@ -0,0 +1,38 @@
+use hdk::prelude::*;
+use posts_integrity::*;
+/// Notifies the agents mentioned in the text of the given Post or Comment.
+pub(crate) fn add_mention_links(target_hash: &ActionHash, text: &str) -> ExternResult<()> {
+    let my_pub_key = agent_info()?.agent_initial_pubkey;
+    for agent in extract_mentions(text) {
+        if agent.eq(&my_pub_key) {
+            continue;
+        }
+        create_link(agent, target_hash.clone(), LinkTypes::AgentToMentions, ())?;
+    }
+    Ok(())
+}
+pub(crate) fn remove_mention_links(target_hash: &ActionHash, text: &str) -> ExternResult<()> {
+    for agent in extract_mentions(text) {
+        let links = get_links(
+            GetLinksInputBuilder::try_new(agent, LinkTypes::AgentToMentions)?.build(),
+        )?;
+        for link in links {
+            if let Some(hash) = link.target.into_action_hash() {
+                if hash.eq(target_hash) {
+                    delete_link(link.create_link_hash)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+/// The posts and comments that mention the calling agent, newest first.
+#[hdk_extern]
+pub fn get_my_mentions(_: ()) -> ExternResult<Vec<Link>> {
+    let my_pub_key = agent_info()?.agent_initial_pubkey;
+    let mut links = get_links(
+        GetLinksInputBuilder::try_new(my_pub_key, LinkTypes::AgentToMentions)?.build(),
+    )?;
+    links.sort_by_key(|link| std::cmp::Reverse(link.timestamp));
+    Ok(links)
+}
@@ -0,0 +1,126 @@
+use hdk::prelude::*;
+use posts_integrity::*;
+/// Flags a post or a comment for the moderators to review.
+#[hdk_extern]
+pub fn report_content(report: Report) -> ExternResult<Record> {
+    let report_hash = create_entry(&EntryTypes::Report(report))?;
+    let path = Path::from(MODERATION_QUEUE_ANCHOR);
+    create_link(path.path_entry_hash()?, report_hash.clone(), LinkTypes::ModerationQueue, ())?;
+    let record = get(report_hash, GetOptions::default())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest(String::from("Could not find the newly created Report"))
+    ))?;
+    Ok(record)
+}
+/// The reports no moderator has resolved yet.
+#[hdk_extern]
+pub fn get_moderation_queue(_: ()) -> ExternResult<Vec<Link>> {
+    ensure_moderator()?;
+    let path = Path::from(MODERATION_QUEUE_ANCHOR);
+    get_links(
+        GetLinksInputBuilder::try_new(path.path_entry_hash()?, LinkTypes::ModerationQueue)?
+            .build(),
+    )
+}
+#[hdk_extern]
+pub fn take_moderation_action(moderation_action: ModerationAction) -> ExternResult<Record> {
+    ensure_moderator()?;
+    let moderation_action_hash =
+        create_entry(&EntryTypes::ModerationAction(moderation_action.clone()))?;
+    create_link(
+        moderation_action.target_hash,
+        moderation_action_hash.clone(),
+        LinkTypes::TargetToModerationActions,
+        moderation_action.kind.link_tag(),
+    )?;
+    let record = get(moderation_action_hash, GetOptions::default())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest(String::from("Could not find the newly created ModerationAction"))
+    ))?;
+    Ok(record)
+}
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ResolveReportInput {
+    pub report_hash: ActionHash,
+    /// What to do about the reported target, `None` to dismiss the report.
+    pub action: Option<ModerationActionKind>,
+    pub reason: String,
+}
+/// Takes the report out of the moderation queue, acting on its target if asked to.
+#[hdk_extern]
+pub fn resolve_report(input: ResolveReportInput) -> ExternResult<Option<Record>> {
+    ensure_moderator()?;
+    let report_record = get(input.report_hash.clone(), GetOptions::default())?.ok_or(
+        wasm_error!(WasmErrorInner::Guest(String::from("Report not found"))),
+    )?;
+    let report = Report::try_from(report_record)?;
+    let moderation_action_record = match input.action {
+        Some(kind) => Some(take_moderation_action(ModerationAction {
+            target_hash: report.target_hash,
+            kind,
+            reason: input.reason,
+            report_hash: Some(input.report_hash.clone()),
+        })?),
+        None => None,
+    };
+    for link in get_moderation_queue(())? {
+        if let Some(hash) = link.target.into_action_hash() {
+            if hash.eq(&input.report_hash) {
+                delete_link(link.create_link_hash)?;
+            }
+        }
+    }
+    Ok(moderation_action_record)
+}
+#[hdk_extern]
+pub fn get_moderation_actions_for_target(target_hash: ActionHash) -> ExternResult<Vec<Link>> {
+    get_links(
+        GetLinksInputBuilder::try_new(target_hash, LinkTypes::TargetToModerationActions)?.build(),
+    )
+}
+/// Whether the latest hide or restore action on the target is a hide.
+pub(crate) fn is_hidden(target_hash: ActionHash) -> ExternResult<bool> {
+    let latest_acceptable_timestamp = crate::latest_acceptable_timestamp()?;
+    let latest_kind = get_moderation_actions_for_target(target_hash)?
+        .into_iter()
+        .filter(|link| link.timestamp <= latest_acceptable_timestamp)
+        .filter_map(|link| {
+            ModerationActionKind::from_link_tag(&link.tag).map(|kind| (link.timestamp, kind))
+        })
+        .filter(|(_, kind)| kind.ne(&ModerationActionKind::Warn))
+        .max_by_key(|(timestamp, _)| *timestamp)
+        .map(|(_, kind)| kind);
+    Ok(latest_kind == Some(ModerationActionKind::Hide))
+}
+/// Options shared by the listings that moderators can hide content from.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct ListingOptions {
+    /// Leave out the targets whose latest moderation action hid them.
+    #[serde(default)]
+    pub exclude_hidden: bool,
+}
+pub(crate) fn apply_listing_options(
+    links: Vec<Link>,
+    options: &ListingOptions,
+) -> ExternResult<Vec<Link>> {
+    if !options.exclude_hidden {
+        return Ok(links);
+    }
+    let mut visible_links = Vec::with_capacity(links.len());
+    for link in links {
+        let Some(target_hash) = link.target.clone().into_action_hash() else {
+            continue;
+        };
+        if !is_hidden(target_hash)? {
+            visible_links.push(link);
+        }
+    }
+    Ok(visible_links)
+}
+fn ensure_moderator() -> ExternResult<()> {
+    let my_pub_key = agent_info()?.agent_initial_pubkey;
+    if !forum_config()?.is_moderator(&my_pub_key) {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "Only moderators can do this"
+        ))));
+    }
+    Ok(())
+}
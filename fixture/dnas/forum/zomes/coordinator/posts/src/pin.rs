@@ -0,0 +1,47 @@
+use hdk::prelude::*;
+use posts_integrity::*;
+fn get_pinned_post_links() -> ExternResult<Vec<Link>> {
+    let path = Path::from(PINNED_POSTS_ANCHOR);
+    get_links(
+        GetLinksInputBuilder::try_new(path.path_entry_hash()?, LinkTypes::PinnedPosts)?.build(),
+    )
+}
+/// Pins the post to the top of the forum. Pinning an already pinned post does nothing.
+#[hdk_extern]
+pub fn pin_post(original_post_hash: ActionHash) -> ExternResult<()> {
+    let already_pinned = get_pinned_post_links()?
+        .into_iter()
+        .any(|link| link.target.into_action_hash().as_ref() == Some(&original_post_hash));
+    if !already_pinned {
+        let path = Path::from(PINNED_POSTS_ANCHOR);
+        create_link(path.path_entry_hash()?, original_post_hash, LinkTypes::PinnedPosts, ())?;
+    }
+    Ok(())
+}
+#[hdk_extern]
+pub fn unpin_post(original_post_hash: ActionHash) -> ExternResult<()> {
+    for link in get_pinned_post_links()? {
+        if let Some(hash) = link.target.into_action_hash() {
+            if hash.eq(&original_post_hash) {
+                delete_link(link.create_link_hash)?;
+            }
+        }
+    }
+    Ok(())
+}
+/// The latest revision of each pinned post, most recently pinned first.
+#[hdk_extern]
+pub fn get_pinned_posts(_: ()) -> ExternResult<Vec<Record>> {
+    let mut links = get_pinned_post_links()?;
+    links.sort_by_key(|link| std::cmp::Reverse(link.timestamp));
+    let mut pinned_posts = Vec::with_capacity(links.len());
+    for link in links {
+        let Some(original_post_hash) = link.target.into_action_hash() else {
+            continue;
+        };
+        if let Some(record) = crate::post::get_latest_post(original_post_hash)? {
+            pinned_posts.push(record);
+        }
+    }
+    Ok(pinned_posts)
+}
@@ -0,0 +1,68 @@
+use hdk::prelude::*;
+use std::collections::BTreeSet;
+use posts_integrity::*;
+#[hdk_extern]
+pub fn create_poll(poll: Poll) -> ExternResult<Record> {
+    let poll_hash = create_entry(&EntryTypes::Poll(poll.clone()))?;
+    create_link(poll.post_hash, poll_hash.clone(), LinkTypes::PostToPolls, ())?;
+    let record = get(poll_hash, GetOptions::default())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest(String::from("Could not find the newly created Poll"))
+    ))?;
+    Ok(record)
+}
+#[hdk_extern]
+pub fn get_polls_for_post(post_hash: ActionHash) -> ExternResult<Vec<Link>> {
+    get_links(GetLinksInputBuilder::try_new(post_hash, LinkTypes::PostToPolls)?.build())
+}
+#[hdk_extern]
+pub fn vote_on_poll(poll_vote: PollVote) -> ExternResult<Record> {
+    let poll_vote_hash = create_entry(&EntryTypes::PollVote(poll_vote.clone()))?;
+    create_link(poll_vote.poll_hash, poll_vote_hash.clone(), LinkTypes::PollToVotes, ())?;
+    let record = get(poll_vote_hash, GetOptions::default())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest(String::from("Could not find the newly created PollVote"))
+    ))?;
+    Ok(record)
+}
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PollResults {
+    pub poll: Poll,
+    /// How many voters picked each option, in the order of the poll's options.
+    pub counts: Vec<u32>,
+    pub voters: u32,
+    pub closed: bool,
+}
+#[hdk_extern]
+pub fn get_poll_results(poll_hash: ActionHash) -> ExternResult<PollResults> {
+    let poll_record = get(poll_hash.clone(), GetOptions::default())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest(String::from("Poll not found"))
+    ))?;
+    let poll = Poll::try_from(poll_record)?;
+    let links = get_links(
+        GetLinksInputBuilder::try_new(poll_hash, LinkTypes::PollToVotes)?.build(),
+    )?;
+    let input = links
+        .into_iter()
+        .filter_map(|link| link.target.into_any_dht_hash())
+        .map(|hash| GetInput::new(hash, GetOptions::default()))
+        .collect();
+    let records = HDK.with(|hdk| hdk.borrow().get(input))?;
+    let mut counts = vec![0; poll.options.len()];
+    let mut voters = BTreeSet::new();
+    for record in records.into_iter().flatten() {
+        if !voters.insert(record.action().author().clone()) {
+            continue;
+        }
+        let poll_vote = PollVote::try_from(record)?;
+        for option_index in poll_vote.option_indices {
+            if let Some(count) = counts.get_mut(option_index) {
+                *count += 1;
+            }
+        }
+    }
+    Ok(PollResults {
+        closed: sys_time()? >= poll.closes_at,
+        poll,
+        counts,
+        voters: voters.len() as u32,
+    })
+}
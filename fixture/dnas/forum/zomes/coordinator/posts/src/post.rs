@@ -2,17 +2,27 @@ use hdk::prelude::*;
 use posts_integrity::*;
 #[hdk_extern]
 pub fn create_post(post: Post) -> ExternResult<Record> {
+    let record = create_unlisted_post(&post)?;
+    if let Some(channel_hash) = &post.channel_hash {
+        crate::channel::add_channel_link(record.action_address(), channel_hash)?;
+    }
+    Ok(record)
+}
+/// Creates the post with all its links except the listing in its channel.
+pub(crate) fn create_unlisted_post(post: &Post) -> ExternResult<Record> {
     let post_hash = create_entry(&EntryTypes::Post(post.clone()))?;
     let record = get(post_hash.clone(), GetOptions::default())?.ok_or(wasm_error!(
         WasmErrorInner::Guest(String::from("Could not find the newly created Post"))
     ))?;
-    let path = Path::from("all_posts");
+    let path = Path::from(ALL_POSTS_ANCHOR);
     create_link(
         path.path_entry_hash()?,
         post_hash.clone(),
         LinkTypes::AllPosts,
         (),
     )?;
+    crate::tag::add_tag_links(&post_hash, &post.tags)?;
+    crate::mention::add_mention_links(&post_hash, &post.content)?;
     Ok(record)
 }
 #[hdk_extern]
@@ -20,8 +30,10 @@ pub fn get_latest_post(original_post_hash: ActionHash) -> ExternResult<Option<Re
     let links = get_links(
         GetLinksInputBuilder::try_new(original_post_hash.clone(), LinkTypes::PostUpdates)?.build(),
     )?;
+    let latest_acceptable_timestamp = crate::latest_acceptable_timestamp()?;
     let latest_link = links
         .into_iter()
+        .filter(|link| link.timestamp <= latest_acceptable_timestamp)
         .max_by(|link_a, link_b| link_a.timestamp.cmp(&link_b.timestamp));
     let latest_post_hash = match latest_link {
         Some(link) => {
@@ -71,7 +83,7 @@ pub fn get_all_revisions_for_post(original_post_hash: ActionHash) -> ExternResul
         })
         .collect::<ExternResult<Vec<GetInput>>>()?;
     let records = HDK.with(|hdk| hdk.borrow().get(get_input))?;
-    let mut records: Vec<Record> = records.into_iter().filter_map(|r| r).collect();
+    let mut records: Vec<Record> = records.into_iter().flatten().collect();
     records.insert(0, original_record);
     Ok(records)
 }
@@ -80,46 +92,191 @@ pub struct UpdatePostInput {
     pub original_post_hash: ActionHash,
     pub previous_post_hash: ActionHash,
     pub updated_post: Post,
+    /// Why the post was edited, shown in its history.
+    #[serde(default)]
+    pub edit_summary: Option<String>,
 }
 #[hdk_extern]
 pub fn update_post(input: UpdatePostInput) -> ExternResult<Record> {
+    let previous_record = get(input.previous_post_hash.clone(), GetOptions::default())?.ok_or(
+        wasm_error!(WasmErrorInner::Guest(String::from("Could not find the previous Post")))
+    )?;
+    let previous_post = Post::try_from(previous_record)?;
     let updated_post_hash = update_entry(input.previous_post_hash.clone(), &input.updated_post)?;
     create_link(
         input.original_post_hash.clone(),
         updated_post_hash.clone(),
         LinkTypes::PostUpdates,
-        (),
+        LinkTag::new(input.edit_summary.unwrap_or_default()),
     )?;
+    let stale_tags: Vec<String> = previous_post
+        .tags
+        .iter()
+        .filter(|tag| !input.updated_post.tags.contains(tag))
+        .cloned()
+        .collect();
+    let new_tags: Vec<String> = input
+        .updated_post
+        .tags
+        .iter()
+        .filter(|tag| !previous_post.tags.contains(tag))
+        .cloned()
+        .collect();
+    crate::tag::remove_tag_links(&input.original_post_hash, &stale_tags)?;
+    crate::tag::add_tag_links(&input.original_post_hash, &new_tags)?;
     let record = get(updated_post_hash.clone(), GetOptions::default())?.ok_or(wasm_error!(
         WasmErrorInner::Guest(String::from("Could not find the newly updated Post"))
     ))?;
     Ok(record)
 }
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PostRevision {
+    pub action_hash: ActionHash,
+    pub author: AgentPubKey,
+    pub timestamp: Timestamp,
+    pub edit_summary: Option<String>,
+}
+/// Every revision of the post, oldest first, read from the PostUpdates links.
+///
+/// Only the original post is fetched: the author, time and summary of each later revision are
+/// those of the link committed along with it.
+#[hdk_extern]
+pub fn get_post_history(original_post_hash: ActionHash) -> ExternResult<Vec<PostRevision>> {
+    let Some(original_record) = get(original_post_hash.clone(), GetOptions::default())? else {
+        return Ok(vec![]);
+    };
+    let mut history = vec![PostRevision {
+        action_hash: original_post_hash.clone(),
+        author: original_record.action().author().clone(),
+        timestamp: original_record.action().timestamp(),
+        edit_summary: None,
+    }];
+    let links = get_links(
+        GetLinksInputBuilder::try_new(original_post_hash, LinkTypes::PostUpdates)?.build(),
+    )?;
+    let latest_acceptable_timestamp = crate::latest_acceptable_timestamp()?;
+    let mut revisions = links
+        .into_iter()
+        .filter(|link| link.timestamp <= latest_acceptable_timestamp)
+        .map(|link| {
+            let action_hash = link.target.into_action_hash().ok_or(wasm_error!(
+                WasmErrorInner::Guest(String::from("No action hash associated with link"))
+            ))?;
+            Ok(PostRevision {
+                action_hash,
+                edit_summary: edit_summary_from_link_tag(&link.tag),
+                author: link.author,
+                timestamp: link.timestamp,
+            })
+        })
+        .collect::<ExternResult<Vec<PostRevision>>>()?;
+    revisions.sort_by_key(|revision| revision.timestamp);
+    history.extend(revisions);
+    Ok(history)
+}
 #[hdk_extern]
 pub fn delete_post(original_post_hash: ActionHash) -> ExternResult<ActionHash> {
     let details =
         get_details(original_post_hash.clone(), GetOptions::default())?.ok_or(wasm_error!(
             WasmErrorInner::Guest(String::from("{pascal_entry_def_name} not found"))
         ))?;
-    let record = match details {
-        Details::Record(details) => Ok(details.record),
+    match details {
+        Details::Record(_) => Ok(()),
         _ => Err(wasm_error!(WasmErrorInner::Guest(String::from(
             "Malformed get details response"
         )))),
     }?;
-    let path = Path::from("all_posts");
+    // Only the post author can remove it from AllPosts; listings skip the posts that
+    // moderators deleted
+    let my_pub_key = agent_info()?.agent_initial_pubkey;
+    let path = Path::from(ALL_POSTS_ANCHOR);
     let links = get_links(
         GetLinksInputBuilder::try_new(path.path_entry_hash()?, LinkTypes::AllPosts)?.build(),
     )?;
     for link in links {
+        if link.author.ne(&my_pub_key) {
+            continue;
+        }
         if let Some(hash) = link.target.into_action_hash() {
             if hash.eq(&original_post_hash) {
                 delete_link(link.create_link_hash)?;
             }
         }
     }
+    if let Some(latest_record) = get_latest_post(original_post_hash.clone())? {
+        let latest_post = Post::try_from(latest_record)?;
+        crate::tag::remove_tag_links(&original_post_hash, &latest_post.tags)?;
+    }
+    crate::channel::remove_channel_links(&original_post_hash)?;
+    if let Some(original_record) = get_original_post(original_post_hash.clone())? {
+        let original_post = Post::try_from(original_record)?;
+        crate::mention::remove_mention_links(&original_post_hash, &original_post.content)?;
+    }
     delete_entry(original_post_hash)
 }
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DeletePostWithCommentsInput {
+    pub original_post_hash: ActionHash,
+    /// Whether to delete the comments themselves, not just their links from the post.
+    pub delete_comments: bool,
+}
+/// What a cascading delete removed along with the post.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CascadeDeleteReport {
+    pub original_post_hash: ActionHash,
+    pub delete_action_hash: ActionHash,
+    /// The `CreateLink` hashes of the PostToComments links that were deleted.
+    pub removed_comment_links: Vec<ActionHash>,
+    pub deleted_comments: Vec<ActionHash>,
+}
+/// Deletes the post like [`delete_post`] along with every live PostToComments link, so the
+/// deleted thread's comments are no longer reachable. With `delete_comments`, the comments
+/// are deleted as well, which the post author and moderators are allowed to do for comments
+/// written by other agents. Anyone else is turned away before anything is deleted.
+#[hdk_extern]
+pub fn delete_post_with_comments(
+    input: DeletePostWithCommentsInput,
+) -> ExternResult<CascadeDeleteReport> {
+    let original_record = get_original_post(input.original_post_hash.clone())?.ok_or(
+        wasm_error!(WasmErrorInner::Guest(String::from("Post not found"))),
+    )?;
+    let my_pub_key = agent_info()?.agent_initial_pubkey;
+    if original_record.action().author().ne(&my_pub_key)
+        && !forum_config()?.is_moderator(&my_pub_key)
+    {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "Only the author of a Post or a moderator can delete it along with its comments"
+        ))));
+    }
+    let links = get_links(
+        GetLinksInputBuilder::try_new(input.original_post_hash.clone(), LinkTypes::PostToComments)?
+            .build(),
+    )?;
+    let mut removed_comment_links = Vec::with_capacity(links.len());
+    let mut deleted_comments = vec![];
+    for link in links {
+        let Some(comment_hash) = link.target.into_action_hash() else {
+            continue;
+        };
+        if input.delete_comments {
+            // Also removes the comment's PostToComments link
+            crate::comment::delete_comment(comment_hash.clone())?;
+            deleted_comments.push(comment_hash);
+        } else {
+            delete_link(link.create_link_hash.clone())?;
+        }
+        removed_comment_links.push(link.create_link_hash);
+    }
+    let delete_action_hash = delete_post(input.original_post_hash.clone())?;
+    let report = CascadeDeleteReport {
+        original_post_hash: input.original_post_hash,
+        delete_action_hash,
+        removed_comment_links,
+        deleted_comments,
+    };
+    emit_signal(crate::Signal::PostDeletedWithComments { report: report.clone() })?;
+    Ok(report)
+}
 #[hdk_extern]
 pub fn get_all_deletes_for_post(
     original_post_hash: ActionHash,
@@ -134,18 +291,27 @@ pub fn get_all_deletes_for_post(
         Details::Record(record_details) => Ok(Some(record_details.deletes)),
     }
 }
+/// The oldest delete of the post, along with the copy it was restored as, if any.
 #[hdk_extern]
 pub fn get_oldest_delete_for_post(
     original_post_hash: ActionHash,
-) -> ExternResult<Option<SignedActionHashed>> {
-    let Some(mut deletes) = get_all_deletes_for_post(original_post_hash)? else {
+) -> ExternResult<Option<crate::restore::DeletionStatus>> {
+    let Some(mut deletes) = get_all_deletes_for_post(original_post_hash.clone())? else {
         return Ok(None);
     };
+    let latest_acceptable_timestamp = crate::latest_acceptable_timestamp()?;
+    deletes.retain(|delete| delete.action().timestamp() <= latest_acceptable_timestamp);
     deletes.sort_by(|delete_a, delete_b| {
         delete_a
             .action()
             .timestamp()
             .cmp(&delete_b.action().timestamp())
     });
-    Ok(deletes.first().cloned())
+    let Some(delete) = deletes.first().cloned() else {
+        return Ok(None);
+    };
+    Ok(Some(crate::restore::DeletionStatus {
+        delete,
+        restored_as: crate::restore::get_restored_as(original_post_hash)?,
+    }))
 }
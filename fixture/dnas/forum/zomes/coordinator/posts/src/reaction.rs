@@ -0,0 +1,73 @@
+use hdk::prelude::*;
+use posts_integrity::*;
+use std::collections::{BTreeMap, BTreeSet};
+#[hdk_extern]
+pub fn add_reaction(reaction: Reaction) -> ExternResult<Record> {
+    let reaction_hash = create_entry(&EntryTypes::Reaction(reaction.clone()))?;
+    create_link(
+        reaction.target_hash.clone(),
+        reaction_hash.clone(),
+        LinkTypes::TargetToReactions,
+        LinkTag::new(reaction.code.as_bytes()),
+    )?;
+    let record = get(reaction_hash.clone(), GetOptions::default())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest(String::from("Could not find the newly created Reaction"))
+    ))?;
+    Ok(record)
+}
+/// Removes the calling agent's reaction on the given target, if any.
+#[hdk_extern]
+pub fn remove_reaction(target_hash: ActionHash) -> ExternResult<()> {
+    let my_pub_key = agent_info()?.agent_initial_pubkey;
+    let links = get_links(
+        GetLinksInputBuilder::try_new(target_hash, LinkTypes::TargetToReactions)?.build(),
+    )?;
+    for link in links {
+        if link.author.ne(&my_pub_key) {
+            continue;
+        }
+        delete_link(link.create_link_hash)?;
+        if let Some(reaction_hash) = link.target.into_action_hash() {
+            delete_entry(reaction_hash)?;
+        }
+    }
+    Ok(())
+}
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ReactionsSummary {
+    /// How many agents reacted with each code.
+    pub counts: BTreeMap<String, usize>,
+    /// The code the calling agent reacted with, if they did.
+    pub my_reaction: Option<String>,
+}
+/// Aggregates the reactions on the given target from the link tags alone, counting only the
+/// latest link of each agent so that extra links can't inflate the counts.
+#[hdk_extern]
+pub fn get_reactions_summary(target_hash: ActionHash) -> ExternResult<ReactionsSummary> {
+    let my_pub_key = agent_info()?.agent_initial_pubkey;
+    let mut links = get_links(
+        GetLinksInputBuilder::try_new(target_hash, LinkTypes::TargetToReactions)?.build(),
+    )?;
+    links.sort_by_key(|link| std::cmp::Reverse(link.timestamp));
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    let mut my_reaction = None;
+    let mut reactors = BTreeSet::new();
+    for link in links {
+        if !reactors.insert(link.author.clone()) {
+            continue;
+        }
+        let code = String::from_utf8(link.tag.into_inner()).map_err(|_| {
+            wasm_error!(WasmErrorInner::Guest(String::from(
+                "Reaction link tag is not a valid reaction code"
+            )))
+        })?;
+        if link.author.eq(&my_pub_key) {
+            my_reaction = Some(code.clone());
+        }
+        *counts.entry(code).or_default() += 1;
+    }
+    Ok(ReactionsSummary {
+        counts,
+        my_reaction,
+    })
+}
@@ -0,0 +1,207 @@
+use hdk::prelude::*;
+use posts_integrity::*;
+/// The delete of a post or comment, along with the copy it was later restored as, if any.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DeletionStatus {
+    pub delete: SignedActionHashed,
+    pub restored_as: Option<ActionHash>,
+}
+/// The first copy the given deleted post or comment was restored as.
+pub(crate) fn get_restored_as(original_hash: ActionHash) -> ExternResult<Option<ActionHash>> {
+    let links = get_links(
+        GetLinksInputBuilder::try_new(original_hash, LinkTypes::RestoredAs)?.build(),
+    )?;
+    let latest_acceptable_timestamp = crate::latest_acceptable_timestamp()?;
+    Ok(links
+        .into_iter()
+        .filter(|link| link.timestamp <= latest_acceptable_timestamp)
+        .min_by_key(|link| link.timestamp)
+        .and_then(|link| link.target.into_action_hash()))
+}
+/// Links the restored copy and the original both ways, returning the hash of the RestoredFrom
+/// link, which proves the restoration to the links re-created on the copy.
+fn link_restoration(
+    original_hash: ActionHash,
+    restored_hash: ActionHash,
+    tag: LinkTag,
+) -> ExternResult<ActionHash> {
+    let restored_from_hash = create_link(
+        restored_hash.clone(),
+        original_hash.clone(),
+        LinkTypes::RestoredFrom,
+        tag.clone(),
+    )?;
+    create_link(original_hash, restored_hash, LinkTypes::RestoredAs, tag)?;
+    Ok(restored_from_hash)
+}
+fn restoration_proof_tag(proof: RestorationProof) -> ExternResult<LinkTag> {
+    let bytes = SerializedBytes::try_from(proof).map_err(|e| wasm_error!(e))?;
+    Ok(LinkTag::new(bytes.bytes().clone()))
+}
+fn restoration_proof_from_link_tag(tag: LinkTag) -> Option<RestorationProof> {
+    if tag.0.is_empty() {
+        return None;
+    }
+    RestorationProof::try_from(SerializedBytes::from(UnsafeBytes::from(tag.into_inner()))).ok()
+}
+/// Every copy the given post or comment was restored as, including the restorations of those
+/// copies, each with the proof that leads back to the given one.
+pub(crate) fn get_restored_copies(
+    original_hash: ActionHash,
+) -> ExternResult<Vec<(ActionHash, RestorationProof)>> {
+    let mut copies: Vec<(ActionHash, RestorationProof)> = vec![];
+    let mut proof: Vec<ActionHash> = vec![];
+    let mut copy_hash = original_hash;
+    while let Some(restored_hash) = get_restored_as(copy_hash.clone())? {
+        if copies.iter().any(|(hash, _)| hash.eq(&restored_hash)) {
+            break;
+        }
+        let links = get_links(
+            GetLinksInputBuilder::try_new(restored_hash.clone(), LinkTypes::RestoredFrom)?.build(),
+        )?;
+        let Some(restored_from) = links
+            .into_iter()
+            .filter(|link| link.target.clone().into_action_hash().as_ref() == Some(&copy_hash))
+            .min_by_key(|link| link.timestamp)
+        else {
+            break;
+        };
+        proof.insert(0, restored_from.create_link_hash);
+        copies.push((restored_hash.clone(), RestorationProof(proof.clone())));
+        copy_hash = restored_hash;
+    }
+    Ok(copies)
+}
+/// Re-creates the comments or replies linked to the deleted original on its restored copy,
+/// extending the proof of those that were already re-linked from an earlier copy.
+fn relink_to_restored_copy(
+    original_hash: ActionHash,
+    restored_hash: ActionHash,
+    restored_from_hash: ActionHash,
+    link_type: LinkTypes,
+) -> ExternResult<()> {
+    let links = get_links(GetLinksInputBuilder::try_new(original_hash, link_type)?.build())?;
+    for link in links {
+        let mut proof = vec![restored_from_hash.clone()];
+        if let Some(previous_proof) = restoration_proof_from_link_tag(link.tag) {
+            proof.extend(previous_proof.0);
+        }
+        create_link(
+            restored_hash.clone(),
+            link.target,
+            link_type,
+            restoration_proof_tag(RestorationProof(proof))?,
+        )?;
+    }
+    Ok(())
+}
+/// Whether the post or comment has a RestoredAs link, counted without fetching the links so
+/// that content which was never restored doesn't pay for walking its copies.
+fn has_restored_copies(original_hash: ActionHash) -> ExternResult<bool> {
+    let link_type = LinkTypeFilter::try_from(LinkTypes::RestoredAs)?;
+    Ok(count_links(LinkQuery::new(original_hash, link_type))? > 0)
+}
+/// Links a comment to the restored copies of its post, or a reply to those of its parent.
+pub(crate) fn link_to_restored_copies(
+    original_hash: ActionHash,
+    comment_hash: ActionHash,
+    link_type: LinkTypes,
+) -> ExternResult<()> {
+    if !has_restored_copies(original_hash.clone())? {
+        return Ok(());
+    }
+    for (copy_hash, proof) in get_restored_copies(original_hash)? {
+        create_link(copy_hash, comment_hash.clone(), link_type, restoration_proof_tag(proof)?)?;
+    }
+    Ok(())
+}
+/// Deletes the links from the restored copies of a post or comment to the given comment.
+pub(crate) fn unlink_from_restored_copies(
+    original_hash: ActionHash,
+    comment_hash: &ActionHash,
+    link_type: LinkTypes,
+) -> ExternResult<()> {
+    if !has_restored_copies(original_hash.clone())? {
+        return Ok(());
+    }
+    for (copy_hash, _) in get_restored_copies(original_hash)? {
+        let links = get_links(GetLinksInputBuilder::try_new(copy_hash, link_type)?.build())?;
+        for link in links {
+            if link.target.into_action_hash().as_ref() == Some(comment_hash) {
+                delete_link(link.create_link_hash)?;
+            }
+        }
+    }
+    Ok(())
+}
+fn ensure_restorable(deletion_status: Option<DeletionStatus>) -> ExternResult<()> {
+    let Some(deletion_status) = deletion_status else {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "Only deleted content can be restored"
+        ))));
+    };
+    if deletion_status.restored_as.is_some() {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "This content has already been restored"
+        ))));
+    }
+    Ok(())
+}
+/// Republishes the latest revision of a deleted post as a new post, adding it back to every
+/// index it was listed in and re-linking its comments. Only the original author or a moderator
+/// can restore a post, and the restored copy is authored by whoever restores it.
+#[hdk_extern]
+pub fn restore_post(original_post_hash: ActionHash) -> ExternResult<Record> {
+    let deletion_status = crate::post::get_oldest_delete_for_post(original_post_hash.clone())?;
+    ensure_restorable(deletion_status)?;
+    let latest_record = crate::post::get_latest_post(original_post_hash.clone())?.ok_or(
+        wasm_error!(WasmErrorInner::Guest(String::from("Post not found"))),
+    )?;
+    // The restoration links name the restored revision, so that validation can check the copy
+    let tag = if latest_record.action_address().eq(&original_post_hash) {
+        LinkTag::new(vec![])
+    } else {
+        LinkTag::new(latest_record.action_address().get_raw_39().to_vec())
+    };
+    let post = Post::try_from(latest_record)?;
+    let record = crate::post::create_unlisted_post(&post)?;
+    let restored_hash = record.action_address().clone();
+    let restored_from_hash =
+        link_restoration(original_post_hash.clone(), restored_hash.clone(), tag)?;
+    crate::channel::add_channel_link_for_restored_copy(
+        &original_post_hash,
+        &restored_hash,
+        restored_from_hash.clone(),
+        &post,
+    )?;
+    relink_to_restored_copy(
+        original_post_hash,
+        restored_hash,
+        restored_from_hash,
+        LinkTypes::PostToComments,
+    )?;
+    Ok(record)
+}
+/// Republishes a deleted comment as a new comment under the same post and parent comment,
+/// re-linking its replies. Only the original author or a moderator can restore a comment.
+#[hdk_extern]
+pub fn restore_comment(original_comment_hash: ActionHash) -> ExternResult<Record> {
+    let deletion_status =
+        crate::comment::get_oldest_delete_for_comment(original_comment_hash.clone())?;
+    ensure_restorable(deletion_status)?;
+    let original_record = crate::comment::get_comment(original_comment_hash.clone())?.ok_or(
+        wasm_error!(WasmErrorInner::Guest(String::from("Comment not found"))),
+    )?;
+    let comment = Comment::try_from(original_record)?;
+    let record = crate::comment::create_comment(comment)?;
+    let restored_hash = record.action_address().clone();
+    let restored_from_hash =
+        link_restoration(original_comment_hash.clone(), restored_hash.clone(), LinkTag::new(vec![]))?;
+    relink_to_restored_copy(
+        original_comment_hash,
+        restored_hash,
+        restored_from_hash,
+        LinkTypes::CommentToReplies,
+    )?;
+    Ok(record)
+}
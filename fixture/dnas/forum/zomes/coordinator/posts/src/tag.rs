@@ -0,0 +1,54 @@
+use hdk::prelude::*;
+use posts_integrity::*;
+pub(crate) fn add_tag_links(original_post_hash: &ActionHash, tags: &[String]) -> ExternResult<()> {
+    for tag in tags {
+        let path = tag_path(tag).typed(LinkTypes::TagPath)?;
+        path.ensure()?;
+        create_link(
+            path.path_entry_hash()?,
+            original_post_hash.clone(),
+            LinkTypes::TagToPosts,
+            LinkTag::new(tag.as_bytes()),
+        )?;
+    }
+    Ok(())
+}
+pub(crate) fn remove_tag_links(
+    original_post_hash: &ActionHash,
+    tags: &[String],
+) -> ExternResult<()> {
+    for tag in tags {
+        let links = get_links(
+            GetLinksInputBuilder::try_new(
+                tag_path(tag).path_entry_hash()?,
+                LinkTypes::TagToPosts,
+            )?
+            .build(),
+        )?;
+        for link in links {
+            if let Some(hash) = link.target.into_action_hash() {
+                if hash.eq(original_post_hash) {
+                    delete_link(link.create_link_hash)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+#[hdk_extern]
+pub fn get_posts_by_tag(tag: String) -> ExternResult<Vec<Link>> {
+    get_links(
+        GetLinksInputBuilder::try_new(tag_path(&tag).path_entry_hash()?, LinkTypes::TagToPosts)?
+            .build(),
+    )
+}
+#[hdk_extern]
+pub fn get_all_tags(_: ()) -> ExternResult<Vec<String>> {
+    let tags_path = Path::from(TAGS_PATH).typed(LinkTypes::TagPath)?;
+    tags_path
+        .children_paths()?
+        .into_iter()
+        .filter_map(|path| path.leaf().map(String::try_from))
+        .collect::<Result<Vec<String>, SerializedBytesError>>()
+        .map_err(|e| wasm_error!(e))
+}
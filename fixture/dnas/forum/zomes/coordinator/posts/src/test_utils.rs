@@ -0,0 +1,24 @@
+//! Zome functions that bypass the coordinator's checks, compiled only with the `test-utils`
+//! feature so that the tests can exercise the integrity zome's validation directly.
+use hdk::prelude::*;
+use posts_integrity::*;
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RawLinkInput {
+    pub base: AnyLinkableHash,
+    pub target: AnyLinkableHash,
+    pub link_type: LinkTypes,
+    #[serde(with = "serde_bytes")]
+    pub tag: Vec<u8>,
+}
+#[hdk_extern]
+pub fn create_raw_link(input: RawLinkInput) -> ExternResult<ActionHash> {
+    create_link(input.base, input.target, input.link_type, LinkTag::new(input.tag))
+}
+#[hdk_extern]
+pub fn delete_raw_link(create_link_hash: ActionHash) -> ExternResult<ActionHash> {
+    delete_link(create_link_hash)
+}
+#[hdk_extern]
+pub fn get_path_hash(path: String) -> ExternResult<EntryHash> {
+    Path::from(path).path_entry_hash()
+}
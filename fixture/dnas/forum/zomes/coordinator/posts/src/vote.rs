@@ -0,0 +1,69 @@
+use hdk::prelude::*;
+use std::collections::BTreeSet;
+use posts_integrity::*;
+/// Casts the calling agent's vote on the vote's target, replacing their previous vote if any.
+#[hdk_extern]
+pub fn cast_vote(vote: Vote) -> ExternResult<Record> {
+    retract_vote(vote.target_hash.clone())?;
+    let vote_hash = create_entry(&EntryTypes::Vote(vote.clone()))?;
+    create_link(
+        vote.target_hash.clone(),
+        vote_hash.clone(),
+        LinkTypes::TargetToVotes,
+        vote.direction.link_tag(),
+    )?;
+    let record = get(vote_hash.clone(), GetOptions::default())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest(String::from("Could not find the newly created Vote"))
+    ))?;
+    Ok(record)
+}
+/// Removes the calling agent's vote on the given target, if any.
+#[hdk_extern]
+pub fn retract_vote(target_hash: ActionHash) -> ExternResult<()> {
+    let my_pub_key = agent_info()?.agent_initial_pubkey;
+    let links = get_links(
+        GetLinksInputBuilder::try_new(target_hash, LinkTypes::TargetToVotes)?.build(),
+    )?;
+    for link in links {
+        if link.author.ne(&my_pub_key) {
+            continue;
+        }
+        delete_link(link.create_link_hash)?;
+        if let Some(vote_hash) = link.target.into_action_hash() {
+            delete_entry(vote_hash)?;
+        }
+    }
+    Ok(())
+}
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Score {
+    pub upvotes: u32,
+    pub downvotes: u32,
+}
+impl Score {
+    pub fn net(&self) -> i64 {
+        i64::from(self.upvotes) - i64::from(self.downvotes)
+    }
+}
+/// Tallies the votes on the given target from the link tags alone, counting only the latest
+/// link of each agent so that extra links to the same vote don't inflate the score.
+#[hdk_extern]
+pub fn get_score(target_hash: ActionHash) -> ExternResult<Score> {
+    let mut links = get_links(
+        GetLinksInputBuilder::try_new(target_hash, LinkTypes::TargetToVotes)?.build(),
+    )?;
+    links.sort_by_key(|link| std::cmp::Reverse(link.timestamp));
+    let mut score = Score::default();
+    let mut voters = BTreeSet::new();
+    for link in links {
+        if !voters.insert(link.author.clone()) {
+            continue;
+        }
+        match VoteDirection::from_link_tag(&link.tag) {
+            Some(VoteDirection::Up) => score.upvotes += 1,
+            Some(VoteDirection::Down) => score.downvotes += 1,
+            None => {}
+        }
+    }
+    Ok(score)
+}
@@ -0,0 +1,6 @@
+pub mod profile;
+use hdk::prelude::*;
+#[hdk_extern]
+pub fn init(_: ()) -> ExternResult<InitCallbackResult> {
+    Ok(InitCallbackResult::Pass)
+}
@@ -0,0 +1,156 @@
+use hdk::prelude::*;
+use profiles_integrity::*;
+#[hdk_extern]
+pub fn create_profile(profile: Profile) -> ExternResult<Record> {
+    let my_pub_key = agent_info()?.agent_initial_pubkey;
+    if get_original_profile_hash(my_pub_key.clone())?.is_some() {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "You already have a Profile, update it instead"
+        ))));
+    }
+    ensure_nickname_available(&profile.nickname, &my_pub_key)?;
+    let profile_hash = create_entry(&EntryTypes::Profile(profile.clone()))?;
+    let record = get(profile_hash.clone(), GetOptions::default())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest(String::from("Could not find the newly created Profile"))
+    ))?;
+    create_link(my_pub_key, profile_hash.clone(), LinkTypes::AgentToProfile, ())?;
+    add_nickname_link(&profile_hash, &profile.nickname)?;
+    Ok(record)
+}
+#[hdk_extern]
+pub fn update_profile(profile: Profile) -> ExternResult<Record> {
+    let my_pub_key = agent_info()?.agent_initial_pubkey;
+    let Some(original_profile_hash) = get_original_profile_hash(my_pub_key.clone())? else {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "You don't have a Profile yet, create it first"
+        ))));
+    };
+    let previous_record = get_latest_profile(original_profile_hash.clone())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest(String::from("Could not find your Profile"))
+    ))?;
+    let previous_profile = Profile::try_from(previous_record)?;
+    let renamed = previous_profile.nickname.ne(&profile.nickname);
+    if renamed {
+        ensure_nickname_available(&profile.nickname, &my_pub_key)?;
+    }
+    let updated_profile_hash = update_entry(original_profile_hash.clone(), &profile)?;
+    // The nickname index is validated against the Profile update that precedes it
+    if renamed {
+        remove_nickname_link(&original_profile_hash, &previous_profile.nickname)?;
+        add_nickname_link(&original_profile_hash, &profile.nickname)?;
+    }
+    get(updated_profile_hash, GetOptions::default())?.ok_or(wasm_error!(WasmErrorInner::Guest(
+        String::from("Could not find the newly updated Profile")
+    )))
+}
+#[hdk_extern]
+pub fn get_agent_profile(agent: AgentPubKey) -> ExternResult<Option<Record>> {
+    let Some(original_profile_hash) = get_original_profile_hash(agent)? else {
+        return Ok(None);
+    };
+    get_latest_profile(original_profile_hash)
+}
+#[hdk_extern]
+pub fn get_my_profile(_: ()) -> ExternResult<Option<Record>> {
+    get_agent_profile(agent_info()?.agent_initial_pubkey)
+}
+/// The agent's current nickname, meant to be called from other zomes.
+#[hdk_extern]
+pub fn get_agent_nickname(agent: AgentPubKey) -> ExternResult<Option<String>> {
+    let Some(record) = get_agent_profile(agent)? else {
+        return Ok(None);
+    };
+    Ok(Some(Profile::try_from(record)?.nickname))
+}
+/// Links to the profiles whose nickname starts with the given text, ignoring case.
+///
+/// The link tags hold the nicknames and the link authors are the profile owners. Since
+/// nicknames are only unique on a best-effort basis, a nickname can appear more than once.
+#[hdk_extern]
+pub fn search_profiles(nickname_prefix: String) -> ExternResult<Vec<Link>> {
+    if nickname_prefix.chars().count() < MIN_NICKNAME_LENGTH {
+        return Err(wasm_error!(WasmErrorInner::Guest(format!(
+            "Searches need at least {MIN_NICKNAME_LENGTH} characters"
+        ))));
+    }
+    let query = nickname_prefix.to_lowercase();
+    let links = get_nickname_links(&nickname_prefix)?;
+    Ok(links
+        .into_iter()
+        .filter(|link| {
+            String::from_utf8(link.tag.clone().into_inner())
+                .is_ok_and(|nickname| nickname.to_lowercase().starts_with(&query))
+        })
+        .collect())
+}
+fn get_original_profile_hash(agent: AgentPubKey) -> ExternResult<Option<ActionHash>> {
+    let links = get_links(GetLinksInputBuilder::try_new(agent, LinkTypes::AgentToProfile)?.build())?;
+    Ok(links
+        .into_iter()
+        .min_by(|link_a, link_b| link_a.timestamp.cmp(&link_b.timestamp))
+        .and_then(|link| link.target.into_action_hash()))
+}
+fn get_latest_profile(original_profile_hash: ActionHash) -> ExternResult<Option<Record>> {
+    let Some(details) = get_details(original_profile_hash, GetOptions::default())? else {
+        return Ok(None);
+    };
+    let Details::Record(details) = details else {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "Malformed get details response"
+        ))));
+    };
+    let Some(latest_update) = details
+        .updates
+        .into_iter()
+        .max_by(|update_a, update_b| update_a.action().timestamp().cmp(&update_b.action().timestamp()))
+    else {
+        return Ok(Some(details.record));
+    };
+    get(latest_update.hashed.hash, GetOptions::default())
+}
+fn get_nickname_links(nickname: &str) -> ExternResult<Vec<Link>> {
+    get_links(
+        GetLinksInputBuilder::try_new(
+            nickname_prefix_path(nickname).path_entry_hash()?,
+            LinkTypes::NicknameToProfile,
+        )?
+        .build(),
+    )
+}
+/// Nicknames are unique regardless of case, on a best-effort basis: this is only checked
+/// against what this agent can see of the DHT, and validation can't enforce it, so two agents
+/// picking the same nickname at once can both get it.
+fn ensure_nickname_available(nickname: &str, my_pub_key: &AgentPubKey) -> ExternResult<()> {
+    let taken = get_nickname_links(nickname)?.into_iter().any(|link| {
+        link.author.ne(my_pub_key)
+            && String::from_utf8(link.tag.into_inner())
+                .is_ok_and(|other| other.eq_ignore_ascii_case(nickname))
+    });
+    if taken {
+        return Err(wasm_error!(WasmErrorInner::Guest(format!(
+            "Nickname {nickname:?} is already taken"
+        ))));
+    }
+    Ok(())
+}
+fn add_nickname_link(original_profile_hash: &ActionHash, nickname: &str) -> ExternResult<()> {
+    let path = nickname_prefix_path(nickname).typed(LinkTypes::NicknamePath)?;
+    path.ensure()?;
+    create_link(
+        path.path_entry_hash()?,
+        original_profile_hash.clone(),
+        LinkTypes::NicknameToProfile,
+        LinkTag::new(nickname.as_bytes()),
+    )?;
+    Ok(())
+}
+fn remove_nickname_link(original_profile_hash: &ActionHash, nickname: &str) -> ExternResult<()> {
+    for link in get_nickname_links(nickname)? {
+        if let Some(hash) = link.target.into_action_hash() {
+            if hash.eq(original_profile_hash) {
+                delete_link(link.create_link_hash)?;
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use hdi::prelude::*;
+use crate::UnitEntryTypes;
+/// How many actions are requested from the author's chain at a time.
+const ACTIVITY_BATCH_SIZE: u32 = 20;
+/// Walks the author's chain backwards from `chain_top` (included), returning every action
+/// committed at or after `since`, newest first.
+pub fn must_get_agent_activity_since(
+    author: &AgentPubKey,
+    chain_top: ActionHash,
+    since: Timestamp,
+) -> ExternResult<Vec<RegisterAgentActivity>> {
+    let mut activity = vec![];
+    let mut next_chain_top = Some(chain_top);
+    while let Some(chain_top) = next_chain_top.take() {
+        let mut batch = must_get_agent_activity(
+            author.clone(),
+            ChainFilter::new(chain_top).take(ACTIVITY_BATCH_SIZE),
+        )?;
+        batch.sort_by_key(|a| std::cmp::Reverse(a.action.action().action_seq()));
+        for item in batch {
+            if item.action.action().timestamp() < since {
+                return Ok(activity);
+            }
+            next_chain_top = item.action.action().prev_action().cloned();
+            activity.push(item);
+        }
+    }
+    Ok(activity)
+}
+/// Whether the action creates an entry of the given type, either as a `Create` or an `Update`.
+pub fn has_entry_type(action: &Action, entry_type: UnitEntryTypes) -> ExternResult<bool> {
+    let Some(EntryType::App(app_entry_def)) = action.entry_type() else {
+        return Ok(false);
+    };
+    let scoped = ScopedEntryDefIndex::try_from(entry_type)?;
+    Ok(
+        app_entry_def.zome_index == scoped.zome_index
+            && app_entry_def.entry_index == scoped.zome_type,
+    )
+}
+/// Whether the action creates a new entry of the given type, updates excluded.
+pub fn is_create_of_type(action: &Action, entry_type: UnitEntryTypes) -> ExternResult<bool> {
+    Ok(matches!(action, Action::Create(_)) && has_entry_type(action, entry_type)?)
+}
+/// Returns the hashes of the entries of the given type that the author created between `since`
+/// and `chain_top` (included) and hasn't deleted since.
+///
+/// Callers pass the timestamp of the entry they look for duplicates on, e.g. the target of a
+/// reaction, so that the walk stops there instead of going through the whole chain.
+pub fn must_get_active_creates_of_type(
+    author: &AgentPubKey,
+    chain_top: ActionHash,
+    since: Timestamp,
+    entry_type: UnitEntryTypes,
+) -> ExternResult<Vec<ActionHash>> {
+    let activity = must_get_agent_activity_since(author, chain_top, since)?;
+    let deleted: Vec<ActionHash> = activity
+        .iter()
+        .filter_map(|item| match item.action.action() {
+            Action::Delete(delete) => Some(delete.deletes_address.clone()),
+            _ => None,
+        })
+        .collect();
+    let mut active = vec![];
+    for item in activity {
+        if is_create_of_type(item.action.action(), entry_type)?
+            && !deleted.contains(item.action.as_hash())
+        {
+            active.push(item.action.as_hash().clone());
+        }
+    }
+    Ok(active)
+}
+/// Counts the entries of the given type created by the author in the `window` that ends with
+/// the given action, the action itself excluded.
+pub fn count_recent_creates(
+    action: &Create,
+    entry_type: UnitEntryTypes,
+    window: std::time::Duration,
+) -> ExternResult<usize> {
+    let since = action.timestamp.saturating_sub(&window);
+    let activity = must_get_agent_activity_since(
+        &action.author,
+        action.prev_action.clone(),
+        since,
+    )?;
+    let mut count = 0;
+    for item in activity {
+        if is_create_of_type(item.action.action(), entry_type)? {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
@@ -0,0 +1,267 @@
+use hdi::prelude::*;
+use crate::ForumConfig;
+/// Length in bytes of the BLAKE2b hash of a file's content.
+pub const CONTENT_HASH_LENGTH: u8 = 32;
+pub const MAX_FILE_NAME_LENGTH: usize = 255;
+/// A slice of a file's content. Identical chunks share an entry hash, but each create still
+/// writes its own action to the author's chain.
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct FileChunk {
+    #[serde(with = "serde_bytes")]
+    pub bytes: Vec<u8>,
+}
+/// Describes a file and the chunks it is made of, in order.
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct FileMetadata {
+    pub name: String,
+    pub mime_type: String,
+    /// In bytes.
+    pub size: u64,
+    pub chunks: Vec<EntryHash>,
+    /// BLAKE2b hash of the whole content.
+    #[serde(with = "serde_bytes")]
+    pub content_hash: Vec<u8>,
+}
+pub fn validate_create_file_chunk(
+    _action: EntryCreationAction,
+    file_chunk: FileChunk,
+    config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    if file_chunk.bytes.is_empty() {
+        return Ok(ValidateCallbackResult::Invalid(String::from("File chunks cannot be empty")));
+    }
+    if file_chunk.bytes.len() > config.max_chunk_size {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                format!("File chunks cannot be larger than {} bytes", config.max_chunk_size),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_update_file_chunk(
+    _action: Update,
+    _file_chunk: FileChunk,
+    _original_action: EntryCreationAction,
+    _original_file_chunk: FileChunk,
+    _config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from("File chunks cannot be updated")))
+}
+pub fn validate_delete_file_chunk(
+    action: Delete,
+    original_action: EntryCreationAction,
+    _original_file_chunk: FileChunk,
+    _config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author.ne(original_action.author()) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only the author of a file chunk can delete it"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_create_file_metadata(
+    action: EntryCreationAction,
+    file_metadata: FileMetadata,
+    config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    // Updates are rejected anyway, so their chunks are never fetched
+    let EntryCreationAction::Create(_) = action else {
+        return Ok(ValidateCallbackResult::Invalid(String::from("File metadata cannot be updated")));
+    };
+    if file_metadata.name.trim().is_empty() {
+        return Ok(ValidateCallbackResult::Invalid(String::from("File name cannot be empty")));
+    }
+    if file_metadata.name.chars().count() > MAX_FILE_NAME_LENGTH {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                format!("File name cannot be longer than {MAX_FILE_NAME_LENGTH} characters"),
+            ),
+        );
+    }
+    if crate::contains_control_characters(&file_metadata.name, false)
+        || file_metadata.name.contains(['/', '\\'])
+    {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("File name cannot contain control characters or path separators"),
+            ),
+        );
+    }
+    let mime_type = &file_metadata.mime_type;
+    let well_formed_mime_type = mime_type.is_ascii()
+        && !mime_type.contains(char::is_whitespace)
+        && mime_type
+            .split_once('/')
+            .is_some_and(|(kind, subtype)| {
+                !kind.is_empty() && !subtype.is_empty() && !subtype.contains('/')
+            });
+    if !well_formed_mime_type {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                format!("{mime_type:?} is not a valid MIME type"),
+            ),
+        );
+    }
+    if file_metadata.size == 0 {
+        return Ok(ValidateCallbackResult::Invalid(String::from("Files cannot be empty")));
+    }
+    if file_metadata.size > config.max_attachment_size {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                format!("Files cannot be larger than {} bytes", config.max_attachment_size),
+            ),
+        );
+    }
+    let expected_chunks = file_metadata.size.div_ceil(config.max_chunk_size as u64);
+    if file_metadata.chunks.len() as u64 != expected_chunks {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                format!(
+                    "A file of {} bytes must be split into {expected_chunks} chunks",
+                    file_metadata.size,
+                ),
+            ),
+        );
+    }
+    // With the chunk count checked, the size only depends on the length of the last chunk
+    let last_chunk_size =
+        file_metadata.size - (expected_chunks - 1) * config.max_chunk_size as u64;
+    let mut content = Vec::with_capacity(file_metadata.size as usize);
+    let last_chunk_index = file_metadata.chunks.len().saturating_sub(1);
+    for (index, chunk_hash) in file_metadata.chunks.into_iter().enumerate() {
+        let entry = must_get_entry(chunk_hash)?;
+        let Ok(chunk) = FileChunk::try_from(entry.content) else {
+            return Ok(
+                ValidateCallbackResult::Invalid(
+                    String::from("The chunks of a file must be FileChunk entries"),
+                ),
+            );
+        };
+        if index < last_chunk_index && chunk.bytes.len() != config.max_chunk_size {
+            return Ok(
+                ValidateCallbackResult::Invalid(
+                    format!(
+                        "Every chunk of a file but the last must hold {} bytes",
+                        config.max_chunk_size,
+                    ),
+                ),
+            );
+        }
+        if index == last_chunk_index && chunk.bytes.len() as u64 != last_chunk_size {
+            return Ok(
+                ValidateCallbackResult::Invalid(
+                    String::from("The size of a file must be the total size of its chunks"),
+                ),
+            );
+        }
+        content.extend(chunk.bytes);
+    }
+    if hash_blake2b(content, CONTENT_HASH_LENGTH)?.ne(&file_metadata.content_hash) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("The content hash of a file must match its chunks"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_update_file_metadata(
+    _action: Update,
+    _file_metadata: FileMetadata,
+    _original_action: EntryCreationAction,
+    _original_file_metadata: FileMetadata,
+    _config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from("File metadata cannot be updated")))
+}
+pub fn validate_delete_file_metadata(
+    action: Delete,
+    original_action: EntryCreationAction,
+    _original_file_metadata: FileMetadata,
+    config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    crate::validate_author_or_moderator(
+        config,
+        &action.author,
+        original_action.author(),
+        "Only the author of a file or a moderator can delete it",
+    )
+}
+pub fn validate_create_link_post_to_attachments(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+    _config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    let post_hash = base_address
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("No action hash associated with link"))
+            ),
+        )?;
+    let post_record = must_get_valid_record(post_hash)?;
+    let _post: crate::Post = post_record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("Linked action must reference an entry"))
+            ),
+        )?;
+    if !matches!(post_record.action(), Action::Create(_)) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("PostToAttachments links must have the original Create of a Post as their base"),
+            ),
+        );
+    }
+    let action_hash = target_address
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("No action hash associated with link"))
+            ),
+        )?;
+    let record = must_get_valid_record(action_hash)?;
+    let _file_metadata: crate::FileMetadata = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("Linked action must reference an entry"))
+            ),
+        )?;
+    if action.author.ne(post_record.action().author()) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only the author of a Post can attach files to it"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_delete_link_post_to_attachments(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+    config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    crate::validate_author_or_moderator(
+        config,
+        &action.author,
+        &original_action.author,
+        "Only the author of a PostToAttachments link or a moderator can delete it",
+    )
+}
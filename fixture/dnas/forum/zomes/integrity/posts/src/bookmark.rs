@@ -0,0 +1,87 @@
+use hdi::prelude::*;
+use crate::{ForumConfig, UnitEntryTypes};
+pub const MAX_BOOKMARK_FOLDER_LENGTH: usize = 64;
+pub const MAX_BOOKMARK_NOTE_LENGTH: usize = 1_000;
+/// A private bookmark on a post or a comment, only ever stored in its author's source chain.
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct Bookmark {
+    pub target_hash: ActionHash,
+    pub folder: Option<String>,
+    pub note: Option<String>,
+}
+pub fn validate_create_bookmark(
+    _action: EntryCreationAction,
+    bookmark: Bookmark,
+    _config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    if let Some(folder) = &bookmark.folder {
+        if folder.trim().is_empty() {
+            return Ok(
+                ValidateCallbackResult::Invalid(String::from("Bookmark folder cannot be empty")),
+            );
+        }
+        if folder.chars().count() > MAX_BOOKMARK_FOLDER_LENGTH {
+            return Ok(
+                ValidateCallbackResult::Invalid(
+                    format!(
+                        "Bookmark folder cannot be longer than {MAX_BOOKMARK_FOLDER_LENGTH} characters"
+                    ),
+                ),
+            );
+        }
+    }
+    if let Some(note) = &bookmark.note {
+        if note.chars().count() > MAX_BOOKMARK_NOTE_LENGTH {
+            return Ok(
+                ValidateCallbackResult::Invalid(
+                    format!(
+                        "Bookmark note cannot be longer than {MAX_BOOKMARK_NOTE_LENGTH} characters"
+                    ),
+                ),
+            );
+        }
+    }
+    let record = must_get_valid_record(bookmark.target_hash)?;
+    if !crate::has_entry_type(record.action(), UnitEntryTypes::Post)?
+        && !crate::has_entry_type(record.action(), UnitEntryTypes::Comment)?
+    {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Bookmarks must target a Post or a Comment"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_update_bookmark(
+    action: Update,
+    _bookmark: Bookmark,
+    original_action: EntryCreationAction,
+    _original_bookmark: Bookmark,
+    _config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author.ne(original_action.author()) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only the author of a Bookmark can update it"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_delete_bookmark(
+    action: Delete,
+    original_action: EntryCreationAction,
+    _original_bookmark: Bookmark,
+    _config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author.ne(original_action.author()) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only the author of a Bookmark can delete it"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
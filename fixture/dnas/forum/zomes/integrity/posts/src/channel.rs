@@ -0,0 +1,360 @@
+use hdi::prelude::*;
+use crate::{ForumConfig, LinkTypes};
+/// The path under which every channel is indexed.
+pub const ALL_CHANNELS_ANCHOR: &str = "all_channels";
+/// The tag of the ChannelToPosts link that lists a restored Post where the deleted original was
+/// last listed, which can be a channel that a moderator moved the original to.
+#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone, PartialEq)]
+pub struct RestoredChannelListing {
+    /// The RestoredFrom link from the restored copy to the original.
+    pub restored_from_hash: ActionHash,
+    /// The ChannelToPosts link that listed the original in the channel.
+    pub channel_link_hash: ActionHash,
+}
+/// A topical area of the forum that posts can be filed under.
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct Channel {
+    pub name: String,
+    pub description: String,
+    /// Agents that can move posts in and out of this channel, besides its author.
+    pub moderators: Vec<AgentPubKey>,
+}
+/// The `CreateLink` behind the given hash if it is valid and of the given type.
+fn must_get_create_link_of_type(
+    create_link_hash: ActionHash,
+    link_type: LinkTypes,
+) -> ExternResult<Option<CreateLink>> {
+    let record = must_get_valid_record(create_link_hash)?;
+    let Action::CreateLink(create_link) = record.action() else {
+        return Ok(None);
+    };
+    let scoped_link_type = LinkTypes::from_type(create_link.zome_index, create_link.link_type)?;
+    if scoped_link_type != Some(link_type) {
+        return Ok(None);
+    }
+    Ok(Some(create_link.clone()))
+}
+/// Whether the tag of a ChannelToPosts link created by `author` proves that the listed Post
+/// is a copy restored by them of a Post that was listed in the same channel.
+fn is_restored_channel_listing(
+    author: &AgentPubKey,
+    channel_hash: &ActionHash,
+    restored_post_hash: &ActionHash,
+    tag: LinkTag,
+) -> ExternResult<bool> {
+    if tag.0.is_empty() {
+        return Ok(false);
+    }
+    let Ok(listing) = RestoredChannelListing::try_from(
+        SerializedBytes::from(UnsafeBytes::from(tag.into_inner())),
+    ) else {
+        return Ok(false);
+    };
+    let Some(restored_from) = must_get_create_link_of_type(
+        listing.restored_from_hash,
+        LinkTypes::RestoredFrom,
+    )? else {
+        return Ok(false);
+    };
+    let Some(channel_link) = must_get_create_link_of_type(
+        listing.channel_link_hash,
+        LinkTypes::ChannelToPosts,
+    )? else {
+        return Ok(false);
+    };
+    Ok(
+        restored_from.author.eq(author)
+            && restored_from.base_address.eq(&AnyLinkableHash::from(restored_post_hash.clone()))
+            && restored_from.target_address.eq(&channel_link.target_address)
+            && channel_link.base_address.eq(&AnyLinkableHash::from(channel_hash.clone())),
+    )
+}
+/// The ChannelToPosts link that a PostToChannel link mirrors, named by its tag.
+pub fn channel_link_hash_from_link_tag(tag: &LinkTag) -> Option<ActionHash> {
+    ActionHash::try_from_raw_39(tag.0.clone()).ok()
+}
+/// Whether the agent can moderate the channel created with the given record.
+///
+/// The channel's author, the moderators listed in its original version and the forum
+/// moderators all qualify.
+pub fn is_channel_moderator(
+    channel_record: &Record,
+    agent: &AgentPubKey,
+    config: &ForumConfig,
+) -> ExternResult<bool> {
+    if channel_record.action().author().eq(agent) || config.is_moderator(agent) {
+        return Ok(true);
+    }
+    let channel = Channel::try_from(channel_record.clone())?;
+    Ok(channel.moderators.contains(agent))
+}
+pub fn must_get_channel_record(channel_hash: ActionHash) -> ExternResult<Record> {
+    let record = must_get_valid_record(channel_hash)?;
+    let _channel: crate::Channel = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("Dependant action must be accompanied by an entry"))
+            ),
+        )?;
+    Ok(record)
+}
+pub fn validate_create_channel(
+    _action: EntryCreationAction,
+    channel: Channel,
+    config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    if channel.name.trim().is_empty() {
+        return Ok(ValidateCallbackResult::Invalid(String::from("Channel name cannot be empty")));
+    }
+    if channel.name.chars().count() > config.max_channel_name_length {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                format!(
+                    "Channel name cannot be longer than {} characters",
+                    config.max_channel_name_length,
+                ),
+            ),
+        );
+    }
+    if crate::contains_control_characters(&channel.name, false) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Channel name cannot contain control characters"),
+            ),
+        );
+    }
+    if channel.description.chars().count() > config.max_content_length {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                format!(
+                    "Channel description cannot be longer than {} characters",
+                    config.max_content_length,
+                ),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_update_channel(
+    action: Update,
+    _channel: Channel,
+    original_action: EntryCreationAction,
+    _original_channel: Channel,
+    config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    let original_create = crate::must_get_original_create(original_action)?;
+    crate::validate_author_or_moderator(
+        config,
+        &action.author,
+        &original_create.author,
+        "Only the author of a Channel or a moderator can update it",
+    )
+}
+pub fn validate_delete_channel(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_channel: Channel,
+    _config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from("Channels cannot be deleted")))
+}
+pub fn validate_create_link_all_channels(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+    _config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    let all_channels_hash = Path::from(ALL_CHANNELS_ANCHOR).path_entry_hash()?;
+    if base_address.ne(&AnyLinkableHash::from(all_channels_hash)) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("AllChannels links must have the all_channels path as their base"),
+            ),
+        );
+    }
+    let action_hash = target_address
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("No action hash associated with link"))
+            ),
+        )?;
+    let record = must_get_channel_record(action_hash)?;
+    let Action::Create(create) = record.action() else {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("AllChannels links must point to the original Create of a Channel"),
+            ),
+        );
+    };
+    if action.author.ne(&create.author) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only the author of a Channel can add it to AllChannels"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_delete_link_all_channels(
+    _action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+    _config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from("AllChannels links cannot be deleted")))
+}
+pub fn validate_create_link_channel_to_posts(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+    config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    let channel_hash = base_address
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("No action hash associated with link"))
+            ),
+        )?;
+    let channel_record = must_get_channel_record(channel_hash.clone())?;
+    if !matches!(channel_record.action(), Action::Create(_)) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("ChannelToPosts links must have the original Create of a Channel as their base"),
+            ),
+        );
+    }
+    let action_hash = target_address
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("No action hash associated with link"))
+            ),
+        )?;
+    let record = must_get_valid_record(action_hash.clone())?;
+    let post: crate::Post = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("Linked action must reference an entry"))
+            ),
+        )?;
+    if !matches!(record.action(), Action::Create(_)) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("ChannelToPosts links must point to the original Create of a Post"),
+            ),
+        );
+    }
+    let filed_by_author = action.author.eq(record.action().author())
+        && post.channel_hash.as_ref() == Some(&channel_hash);
+    if !filed_by_author
+        && !is_channel_moderator(&channel_record, &action.author, config)?
+        && !is_restored_channel_listing(&action.author, &channel_hash, &action_hash, tag)?
+    {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only channel moderators can file a Post in a channel other than its own"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_delete_link_channel_to_posts(
+    action: DeleteLink,
+    original_action: CreateLink,
+    base: AnyLinkableHash,
+    target: AnyLinkableHash,
+    _tag: LinkTag,
+    config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author.eq(&original_action.author) {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    let post_hash = target
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("No action hash associated with link"))
+            ),
+        )?;
+    if action.author.eq(must_get_action(post_hash)?.action().author()) {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    let channel_hash = base
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("No action hash associated with link"))
+            ),
+        )?;
+    let channel_record = must_get_channel_record(channel_hash)?;
+    if !is_channel_moderator(&channel_record, &action.author, config)? {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only the link author, the Post author or a channel moderator can delete this link"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+/// PostToChannel links mirror the ChannelToPosts links, so that the channels a post is listed
+/// in can be found from the post. The link tag holds the hash of the mirrored link.
+pub fn validate_create_link_post_to_channel(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+    _config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    let Some(channel_link_hash) = channel_link_hash_from_link_tag(&tag) else {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("PostToChannel links must have the ChannelToPosts link they mirror as their tag"),
+            ),
+        );
+    };
+    let Some(channel_link) = must_get_create_link_of_type(
+        channel_link_hash,
+        LinkTypes::ChannelToPosts,
+    )? else {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("PostToChannel links must have the ChannelToPosts link they mirror as their tag"),
+            ),
+        );
+    };
+    if channel_link.base_address.ne(&target_address)
+        || channel_link.target_address.ne(&base_address)
+        || channel_link.author.ne(&action.author)
+    {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("PostToChannel links must mirror a ChannelToPosts link of their author"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+/// Whoever can delete a ChannelToPosts link can delete the PostToChannel link mirroring it.
+pub fn validate_delete_link_post_to_channel(
+    action: DeleteLink,
+    original_action: CreateLink,
+    base: AnyLinkableHash,
+    target: AnyLinkableHash,
+    tag: LinkTag,
+    config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    validate_delete_link_channel_to_posts(action, original_action, target, base, tag, config)
+}
@@ -1,14 +1,57 @@
 use hdi::prelude::*;
+use crate::ForumConfig;
 #[hdk_entry_helper]
 #[derive(Clone, PartialEq)]
 pub struct Comment {
     pub comment: String,
     pub post_hash: ActionHash,
+    /// The comment this one replies to, `None` for top-level comments.
+    #[serde(default)]
+    pub parent_comment_hash: Option<ActionHash>,
 }
 pub fn validate_create_comment(
-    _action: EntryCreationAction,
+    action: EntryCreationAction,
     comment: Comment,
+    config: &ForumConfig,
 ) -> ExternResult<ValidateCallbackResult> {
+    if comment.comment.trim().is_empty() {
+        return Ok(ValidateCallbackResult::Invalid(String::from("Comment cannot be empty")));
+    }
+    if comment.comment.chars().count() > config.max_comment_length {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                format!("Comment cannot be longer than {} characters", config.max_comment_length),
+            ),
+        );
+    }
+    if crate::contains_control_characters(&comment.comment, true) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Comment cannot contain control characters"),
+            ),
+        );
+    }
+    if let (EntryCreationAction::Create(create), Some(rate_limit)) = (
+        &action,
+        &config.rate_limit,
+    ) {
+        let recent_comments = crate::count_recent_creates(
+            create,
+            crate::UnitEntryTypes::Comment,
+            rate_limit.window(),
+        )?;
+        if recent_comments >= rate_limit.max_comments {
+            return Ok(
+                ValidateCallbackResult::Invalid(
+                    format!(
+                        "Cannot create more than {} comments every {} seconds",
+                        rate_limit.max_comments,
+                        rate_limit.window_seconds,
+                    ),
+                ),
+            );
+        }
+    }
     let record = must_get_valid_record(comment.post_hash.clone())?;
     let _post: crate::Post = record
         .entry()
@@ -19,37 +62,134 @@ pub fn validate_create_comment(
                 WasmErrorInner::Guest(String::from("Dependant action must be accompanied by an entry"))
             ),
         )?;
+    if action.timestamp() < &record.action().timestamp() {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("A Comment cannot be dated before the Post it comments on"),
+            ),
+        );
+    }
+    if let Some(parent_comment_hash) = comment.parent_comment_hash {
+        let parent_comment = must_get_comment(parent_comment_hash)?;
+        if parent_comment.post_hash.ne(&comment.post_hash) {
+            return Ok(
+                ValidateCallbackResult::Invalid(
+                    String::from("A reply must belong to the same Post as its parent Comment"),
+                ),
+            );
+        }
+        let mut depth = 1;
+        let mut ancestor = parent_comment;
+        while let Some(ancestor_hash) = ancestor.parent_comment_hash {
+            depth += 1;
+            if depth > config.max_comment_depth {
+                break;
+            }
+            ancestor = must_get_comment(ancestor_hash)?;
+        }
+        if depth > config.max_comment_depth {
+            return Ok(
+                ValidateCallbackResult::Invalid(
+                    format!("Replies cannot be nested more than {} levels deep", config.max_comment_depth),
+                ),
+            );
+        }
+    }
     Ok(ValidateCallbackResult::Valid)
 }
+fn must_get_comment(comment_hash: ActionHash) -> ExternResult<Comment> {
+    let record = must_get_valid_record(comment_hash)?;
+    record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("Dependant action must be accompanied by an entry"))
+            ),
+        )
+}
+/// Whether the agent authored the Post the given Comment belongs to, letting them clear the
+/// comments of their own threads.
+pub fn is_author_of_commented_post(agent: &AgentPubKey, comment: &Comment) -> ExternResult<bool> {
+    let post_action = must_get_action(comment.post_hash.clone())?;
+    Ok(post_action.action().author().eq(agent))
+}
+/// Same as [`is_author_of_commented_post`] for the Comment behind the given action hash,
+/// `false` if it isn't a Comment.
+pub fn is_author_of_commented_post_for(
+    agent: &AgentPubKey,
+    comment_hash: AnyLinkableHash,
+) -> ExternResult<bool> {
+    let Some(comment_hash) = comment_hash.into_action_hash() else {
+        return Ok(false);
+    };
+    let record = must_get_valid_record(comment_hash)?;
+    if !crate::has_entry_type(record.action(), crate::UnitEntryTypes::Comment)? {
+        return Ok(false);
+    }
+    let comment = Comment::try_from(record)?;
+    is_author_of_commented_post(agent, &comment)
+}
+/// Whether the given agent wrote the Comment behind the target of a PostToComments or
+/// CommentToReplies link, who may always unlink it, including from a restored copy.
+fn is_author_of_linked_comment(
+    agent: &AgentPubKey,
+    comment_hash: AnyLinkableHash,
+) -> ExternResult<bool> {
+    let Some(comment_hash) = comment_hash.into_action_hash() else {
+        return Ok(false);
+    };
+    let comment_action = must_get_action(comment_hash)?;
+    Ok(comment_action.action().author().eq(agent))
+}
 pub fn validate_update_comment(
     _action: Update,
     _comment: Comment,
     _original_action: EntryCreationAction,
     _original_comment: Comment,
+    _config: &ForumConfig,
 ) -> ExternResult<ValidateCallbackResult> {
     Ok(ValidateCallbackResult::Invalid(String::from("Comments cannot be updated")))
 }
 pub fn validate_delete_comment(
-    _action: Delete,
-    _original_action: EntryCreationAction,
-    _original_comment: Comment,
+    action: Delete,
+    original_action: EntryCreationAction,
+    original_comment: Comment,
+    config: &ForumConfig,
 ) -> ExternResult<ValidateCallbackResult> {
-    Ok(ValidateCallbackResult::Valid)
+    if &action.timestamp < original_action.timestamp() {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("A delete cannot be dated before the Comment it deletes"),
+            ),
+        );
+    }
+    if is_author_of_commented_post(&action.author, &original_comment)? {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    crate::validate_author_or_moderator(
+        config,
+        &action.author,
+        original_action.author(),
+        "Only the author of a Comment, the author of its Post or a moderator can delete it",
+    )
 }
 pub fn validate_create_link_post_to_comments(
-    _action: CreateLink,
+    action: CreateLink,
     base_address: AnyLinkableHash,
     target_address: AnyLinkableHash,
-    _tag: LinkTag,
+    tag: LinkTag,
+    config: &ForumConfig,
 ) -> ExternResult<ValidateCallbackResult> {
-    let action_hash = base_address
+    let post_hash = base_address
         .into_action_hash()
         .ok_or(
             wasm_error!(
                 WasmErrorInner::Guest(String::from("No action hash associated with link"))
             ),
         )?;
-    let record = must_get_valid_record(action_hash)?;
+    let record = must_get_valid_record(post_hash.clone())?;
     let _post: crate::Post = record
         .entry()
         .to_app_option()
@@ -67,7 +207,7 @@ pub fn validate_create_link_post_to_comments(
             ),
         )?;
     let record = must_get_valid_record(action_hash)?;
-    let _comment: crate::Comment = record
+    let comment: crate::Comment = record
         .entry()
         .to_app_option()
         .map_err(|e| wasm_error!(e))?
@@ -76,14 +216,127 @@ pub fn validate_create_link_post_to_comments(
                 WasmErrorInner::Guest(String::from("Linked action must reference an entry"))
             ),
         )?;
+    if comment.post_hash.ne(&post_hash) {
+        return crate::validate_link_to_restored_copy(
+            &action,
+            post_hash,
+            &comment.post_hash,
+            record.action().author(),
+            tag,
+            config,
+            "PostToComments links must have the commented Post as their base",
+        );
+    }
+    if action.author.ne(record.action().author()) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only the author of a Comment can link it to its Post"),
+            ),
+        );
+    }
     Ok(ValidateCallbackResult::Valid)
 }
 pub fn validate_delete_link_post_to_comments(
-    _action: DeleteLink,
-    _original_action: CreateLink,
-    _base: AnyLinkableHash,
-    _target: AnyLinkableHash,
+    action: DeleteLink,
+    original_action: CreateLink,
+    base: AnyLinkableHash,
+    target: AnyLinkableHash,
     _tag: LinkTag,
+    config: &ForumConfig,
 ) -> ExternResult<ValidateCallbackResult> {
+    if is_author_of_linked_comment(&action.author, target.clone())?
+        || is_author_of_commented_post_for(&action.author, target)?
+    {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    if let Some(post_hash) = base.into_action_hash() {
+        let post_action = must_get_action(post_hash)?;
+        if post_action.action().author().eq(&action.author) {
+            return Ok(ValidateCallbackResult::Valid);
+        }
+    }
+    crate::validate_author_or_moderator(
+        config,
+        &action.author,
+        &original_action.author,
+        "Only the author of a PostToComments link, of its Comment, of its Post or a moderator can delete it",
+    )
+}
+pub fn validate_create_link_comment_to_replies(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+    config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    let parent_comment_hash = base_address
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("No action hash associated with link"))
+            ),
+        )?;
+    let action_hash = target_address
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("No action hash associated with link"))
+            ),
+        )?;
+    let record = must_get_valid_record(action_hash)?;
+    let reply: crate::Comment = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("Linked action must reference an entry"))
+            ),
+        )?;
+    let Some(replied_comment_hash) = reply.parent_comment_hash else {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("CommentToReplies links must have the replied Comment as their base"),
+            ),
+        );
+    };
+    if replied_comment_hash.ne(&parent_comment_hash) {
+        return crate::validate_link_to_restored_copy(
+            &action,
+            parent_comment_hash,
+            &replied_comment_hash,
+            record.action().author(),
+            tag,
+            config,
+            "CommentToReplies links must have the replied Comment as their base",
+        );
+    }
+    if action.author.ne(record.action().author()) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only the author of a reply can link it to its parent Comment"),
+            ),
+        );
+    }
     Ok(ValidateCallbackResult::Valid)
 }
+pub fn validate_delete_link_comment_to_replies(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    target: AnyLinkableHash,
+    _tag: LinkTag,
+    config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    if is_author_of_linked_comment(&action.author, target.clone())?
+        || is_author_of_commented_post_for(&action.author, target)?
+    {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    crate::validate_author_or_moderator(
+        config,
+        &action.author,
+        &original_action.author,
+        "Only the author of a CommentToReplies link, of its reply, of its Post or a moderator can delete it",
+    )
+}
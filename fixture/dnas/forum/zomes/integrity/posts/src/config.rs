@@ -0,0 +1,108 @@
+use hdi::prelude::*;
+/// Who can join the forum's network.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Membership {
+    #[default]
+    Open,
+    InviteOnly,
+}
+/// How many posts and comments an agent may create within a sliding time window.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RateLimit {
+    pub window_seconds: u64,
+    pub max_posts: usize,
+    pub max_comments: usize,
+}
+impl RateLimit {
+    pub fn window(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.window_seconds)
+    }
+}
+/// Forum-wide settings, read from the DNA properties.
+///
+/// Every field is optional in the properties: missing fields take their default value,
+/// and a DNA without properties gets `ForumConfig::default()`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct ForumConfig {
+    pub max_title_length: usize,
+    pub max_content_length: usize,
+    pub max_comment_length: usize,
+    pub max_edit_summary_length: usize,
+    pub max_channel_name_length: usize,
+    pub max_tags_per_post: usize,
+    pub max_poll_options: usize,
+    pub max_tag_length: usize,
+    /// How many levels of replies a top-level comment can have.
+    pub max_comment_depth: usize,
+    pub moderators: Vec<AgentPubKey>,
+    pub progenitor: Option<AgentPubKey>,
+    pub membership: Membership,
+    /// Disabled when `None`, which is the default: forums opt in through their properties.
+    pub rate_limit: Option<RateLimit>,
+    /// The reaction codes agents can choose from.
+    pub allowed_reactions: Vec<String>,
+    /// How far ahead of the reader's clock an action may be dated before it is ignored.
+    pub max_future_skew_seconds: u64,
+    /// In bytes, the largest file that can be attached to a post.
+    pub max_attachment_size: u64,
+    /// In bytes, how much of a file each chunk holds. Every chunk but the last must be full.
+    pub max_chunk_size: usize,
+}
+impl Default for ForumConfig {
+    fn default() -> Self {
+        Self {
+            max_title_length: 200,
+            max_content_length: 10_000,
+            max_comment_length: 2_000,
+            max_edit_summary_length: 200,
+            max_comment_depth: 8,
+            max_channel_name_length: 64,
+            max_tags_per_post: 5,
+            max_poll_options: 10,
+            max_tag_length: 32,
+            moderators: vec![],
+            progenitor: None,
+            membership: Membership::Open,
+            rate_limit: None,
+            allowed_reactions: ["thumbs_up", "thumbs_down", "heart", "laugh", "hooray", "eyes"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            max_future_skew_seconds: 300,
+            max_attachment_size: 10 * 1024 * 1024,
+            max_chunk_size: 256 * 1024,
+        }
+    }
+}
+impl ForumConfig {
+    /// The progenitor is always considered a moderator.
+    pub fn is_moderator(&self, agent: &AgentPubKey) -> bool {
+        self.moderators.contains(agent) || self.progenitor.as_ref() == Some(agent)
+    }
+    pub fn max_future_skew(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.max_future_skew_seconds)
+    }
+}
+pub fn forum_config() -> ExternResult<ForumConfig> {
+    let properties = dna_info()?.modifiers.properties;
+    let config: Option<ForumConfig> = holochain_serialized_bytes::decode(
+            properties.bytes(),
+        )
+        .map_err(|e| wasm_error!(e))?;
+    Ok(config.unwrap_or_default())
+}
+/// Valid if the agent performing the deletion is the original author or a moderator.
+pub fn validate_author_or_moderator(
+    config: &ForumConfig,
+    agent: &AgentPubKey,
+    original_author: &AgentPubKey,
+    reason: &str,
+) -> ExternResult<ValidateCallbackResult> {
+    if agent == original_author || config.is_moderator(agent) {
+        Ok(ValidateCallbackResult::Valid)
+    } else {
+        Ok(ValidateCallbackResult::Invalid(reason.to_string()))
+    }
+}
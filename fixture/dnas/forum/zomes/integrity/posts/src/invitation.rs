@@ -0,0 +1,76 @@
+use hdi::prelude::*;
+use crate::{ForumConfig, Membership};
+/// Permission for `invitee` to join an invite-only forum, granted by `inviter`.
+#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone, PartialEq)]
+pub struct Invitation {
+    pub invitee: AgentPubKey,
+    pub inviter: AgentPubKey,
+    /// The forum the invitation is for, so that it can't be used to join another forum
+    /// sharing the same moderators.
+    pub dna_hash: DnaHash,
+}
+/// The membrane proof expected by invite-only forums.
+#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone, PartialEq)]
+pub struct SignedInvitation {
+    pub invitation: Invitation,
+    pub signature: Signature,
+}
+/// The progenitor and the moderators are the agents allowed to invite others.
+pub fn validate_membrane_proof(
+    agent_pub_key: &AgentPubKey,
+    membrane_proof: &Option<MembraneProof>,
+    config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    if config.membership == Membership::Open {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    if config.progenitor.as_ref() == Some(agent_pub_key) {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    let Some(membrane_proof) = membrane_proof else {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("An invitation is required to join this forum"),
+            ),
+        );
+    };
+    let Ok(signed_invitation) = SignedInvitation::try_from(
+        SerializedBytes::clone(membrane_proof),
+    ) else {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Membrane proof must be a serialized SignedInvitation"),
+            ),
+        );
+    };
+    let SignedInvitation { invitation, signature } = signed_invitation;
+    if invitation.invitee.ne(agent_pub_key) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("The invitation was issued for a different agent"),
+            ),
+        );
+    }
+    if invitation.dna_hash.ne(&dna_info()?.hash) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("The invitation was issued for a different forum"),
+            ),
+        );
+    }
+    if !config.is_moderator(&invitation.inviter) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("The inviter is not allowed to invite agents to this forum"),
+            ),
+        );
+    }
+    if !verify_signature(invitation.inviter.clone(), signature, &invitation)? {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("The invitation is not signed by its inviter"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
@@ -1,15 +1,56 @@
+pub mod activity;
+pub use activity::*;
+pub mod attachment;
+pub use attachment::*;
+pub mod bookmark;
+pub use bookmark::*;
+pub mod channel;
+pub use channel::*;
 pub mod comment;
 pub use comment::*;
+pub mod config;
+pub use config::*;
+pub mod invitation;
+pub use invitation::*;
+pub mod mention;
+pub use mention::*;
+pub mod moderation;
+pub use moderation::*;
+pub mod pin;
+pub use pin::*;
+pub mod poll;
+pub use poll::*;
 pub mod post;
 use hdi::prelude::*;
 pub use post::*;
-#[derive(Serialize, Deserialize)]
+pub mod reaction;
+pub use reaction::*;
+pub mod restore;
+pub use restore::*;
+pub mod tag;
+pub use tag::*;
+pub mod text;
+pub use text::*;
+pub mod vote;
+pub use vote::*;
+#[derive(Serialize, Deserialize, Clone)]
 #[serde(tag = "type")]
 #[hdk_entry_types]
 #[unit_enum(UnitEntryTypes)]
 pub enum EntryTypes {
     Post(Post),
     Comment(Comment),
+    Reaction(Reaction),
+    Vote(Vote),
+    Channel(Channel),
+    FileChunk(FileChunk),
+    FileMetadata(FileMetadata),
+    Poll(Poll),
+    PollVote(PollVote),
+    #[entry_type(visibility = "private")]
+    Bookmark(Bookmark),
+    Report(Report),
+    ModerationAction(ModerationAction),
 }
 #[derive(Serialize, Deserialize)]
 #[hdk_link_types]
@@ -17,14 +58,823 @@ pub enum LinkTypes {
     PostUpdates,
     PostToComments,
     AllPosts,
+    CommentToReplies,
+    TargetToReactions,
+    TargetToVotes,
+    TagPath,
+    TagToPosts,
+    AllChannels,
+    ChannelToPosts,
+    AgentToMentions,
+    PostToAttachments,
+    PostToPolls,
+    PollToVotes,
+    ModerationQueue,
+    TargetToModerationActions,
+    PinnedPosts,
+    RestoredFrom,
+    RestoredAs,
+    PostToChannel,
 }
 #[hdk_extern]
-pub fn genesis_self_check(_data: GenesisSelfCheckData) -> ExternResult<ValidateCallbackResult> {
-    Ok(ValidateCallbackResult::Valid)
+pub fn genesis_self_check(data: GenesisSelfCheckData) -> ExternResult<ValidateCallbackResult> {
+    validate_membrane_proof(&data.agent_key, &data.membrane_proof, &forum_config()?)
 }
 pub fn validate_agent_joining(
-    _agent_pub_key: AgentPubKey,
-    _membrane_proof: &Option<MembraneProof>,
+    agent_pub_key: AgentPubKey,
+    membrane_proof: &Option<MembraneProof>,
+    config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    validate_membrane_proof(&agent_pub_key, membrane_proof, config)
+}
+#[hdk_extern]
+pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
+    let config = forum_config()?;
+    match op.flattened::<EntryTypes, LinkTypes>()? {
+        FlatOp::StoreEntry(store_entry) => match store_entry {
+            OpEntry::CreateEntry { app_entry, action } => {
+                validate_create_entry(EntryCreationAction::Create(action), app_entry, &config)
+            }
+            OpEntry::UpdateEntry { app_entry, action, .. } => {
+                validate_create_entry(EntryCreationAction::Update(action), app_entry, &config)
+            }
+            _ => Ok(ValidateCallbackResult::Valid),
+        },
+        FlatOp::RegisterUpdate(update_entry) => match update_entry {
+            OpUpdate::Entry { app_entry, action } => {
+                let original_record = must_get_valid_record(
+                    action.original_action_address.clone(),
+                )?;
+                validate_update_entry(action, app_entry, original_record, &config)
+            }
+            _ => Ok(ValidateCallbackResult::Valid),
+        },
+        FlatOp::RegisterDelete(delete_entry) => {
+            validate_delete(delete_entry.action, &config)
+        }
+        FlatOp::RegisterCreateLink {
+            link_type,
+            base_address,
+            target_address,
+            tag,
+            action,
+        } => {
+            validate_create_link(
+                link_type,
+                action,
+                base_address,
+                target_address,
+                tag,
+                &config,
+            )
+        }
+        FlatOp::RegisterDeleteLink {
+            link_type,
+            base_address,
+            target_address,
+            tag,
+            original_action,
+            action,
+        } => {
+            validate_delete_link(
+                link_type,
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+                &config,
+            )
+        }
+        FlatOp::StoreRecord(store_record) => match store_record {
+            OpRecord::CreateEntry { app_entry, action } => {
+                validate_create_entry(EntryCreationAction::Create(action), app_entry, &config)
+            }
+            OpRecord::UpdateEntry { original_action_hash, app_entry, action, .. } => {
+                let result = validate_create_entry(
+                    EntryCreationAction::Update(action.clone()),
+                    app_entry.clone(),
+                    &config,
+                )?;
+                let ValidateCallbackResult::Valid = result else {
+                    return Ok(result);
+                };
+                let original_record = must_get_valid_record(original_action_hash)?;
+                validate_update_entry(action, app_entry, original_record, &config)
+            }
+            OpRecord::DeleteEntry { action, .. } => validate_delete(action, &config),
+            OpRecord::CreateLink {
+                base_address,
+                target_address,
+                tag,
+                link_type,
+                action,
+            } => {
+                validate_create_link(
+                    link_type,
+                    action,
+                    base_address,
+                    target_address,
+                    tag,
+                    &config,
+                )
+            }
+            OpRecord::DeleteLink { original_action_hash, base_address, action } => {
+                let record = must_get_valid_record(original_action_hash)?;
+                let create_link = match record.action() {
+                    Action::CreateLink(create_link) => create_link.clone(),
+                    _ => {
+                        return Ok(
+                            ValidateCallbackResult::Invalid(
+                                "The action that a DeleteLink deletes must be a CreateLink"
+                                    .to_string(),
+                            ),
+                        );
+                    }
+                };
+                let Some(link_type) = LinkTypes::from_type(
+                    create_link.zome_index,
+                    create_link.link_type,
+                )? else {
+                    return Ok(ValidateCallbackResult::Valid);
+                };
+                validate_delete_link(
+                    link_type,
+                    action,
+                    create_link.clone(),
+                    base_address,
+                    create_link.target_address,
+                    create_link.tag,
+                    &config,
+                )
+            }
+            _ => Ok(ValidateCallbackResult::Valid),
+        },
+        FlatOp::RegisterAgentActivity(agent_activity) => match agent_activity {
+            OpActivity::CreateAgent { agent, action } => {
+                let previous_action = must_get_action(action.prev_action)?;
+                match previous_action.action() {
+                    Action::AgentValidationPkg(
+                        AgentValidationPkg { membrane_proof, .. },
+                    ) => validate_agent_joining(agent, membrane_proof, &config),
+                    _ => {
+                        Ok(
+                            ValidateCallbackResult::Invalid(
+                                "The previous action for a `CreateAgent` action must be an `AgentValidationPkg`"
+                                    .to_string(),
+                            ),
+                        )
+                    }
+                }
+            }
+            _ => Ok(ValidateCallbackResult::Valid),
+        },
+    }
+}
+fn validate_create_entry(
+    action: EntryCreationAction,
+    app_entry: EntryTypes,
+    config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    match app_entry {
+        EntryTypes::Post(post) => validate_create_post(action, post, config),
+        EntryTypes::Comment(comment) => validate_create_comment(action, comment, config),
+        EntryTypes::Reaction(reaction) => validate_create_reaction(action, reaction, config),
+        EntryTypes::Vote(vote) => validate_create_vote(action, vote, config),
+        EntryTypes::Channel(channel) => validate_create_channel(action, channel, config),
+        EntryTypes::FileChunk(file_chunk) => validate_create_file_chunk(action, file_chunk, config),
+        EntryTypes::FileMetadata(file_metadata) => {
+            validate_create_file_metadata(action, file_metadata, config)
+        }
+        EntryTypes::Poll(poll) => validate_create_poll(action, poll, config),
+        EntryTypes::PollVote(poll_vote) => validate_create_poll_vote(action, poll_vote, config),
+        EntryTypes::Bookmark(bookmark) => validate_create_bookmark(action, bookmark, config),
+        EntryTypes::Report(report) => validate_create_report(action, report, config),
+        EntryTypes::ModerationAction(moderation_action) => {
+            validate_create_moderation_action(action, moderation_action, config)
+        }
+    }
+}
+fn validate_update_entry(
+    action: Update,
+    app_entry: EntryTypes,
+    original_record: Record,
+    config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    let original_action = match EntryCreationAction::try_from(
+        original_record.action().clone(),
+    ) {
+        Ok(action) => action,
+        Err(e) => {
+            return Ok(
+                ValidateCallbackResult::Invalid(
+                    format!("Expected to get EntryCreationAction from Action: {e:?}"),
+                ),
+            );
+        }
+    };
+    match app_entry {
+        EntryTypes::Post(post) => {
+            let original_post = match Post::try_from(original_record) {
+                Ok(entry) => entry,
+                Err(e) => {
+                    return Ok(
+                        ValidateCallbackResult::Invalid(
+                            format!("Expected to get Post from Record: {e:?}"),
+                        ),
+                    );
+                }
+            };
+            validate_update_post(action, post, original_action, original_post, config)
+        }
+        EntryTypes::Comment(comment) => {
+            let original_comment = match Comment::try_from(original_record) {
+                Ok(entry) => entry,
+                Err(e) => {
+                    return Ok(
+                        ValidateCallbackResult::Invalid(
+                            format!("Expected to get Comment from Record: {e:?}"),
+                        ),
+                    );
+                }
+            };
+            validate_update_comment(action, comment, original_action, original_comment, config)
+        }
+        EntryTypes::Reaction(reaction) => {
+            let original_reaction = match Reaction::try_from(original_record) {
+                Ok(entry) => entry,
+                Err(e) => {
+                    return Ok(
+                        ValidateCallbackResult::Invalid(
+                            format!("Expected to get Reaction from Record: {e:?}"),
+                        ),
+                    );
+                }
+            };
+            validate_update_reaction(
+                action,
+                reaction,
+                original_action,
+                original_reaction,
+                config,
+            )
+        }
+        EntryTypes::Vote(vote) => {
+            let original_vote = match Vote::try_from(original_record) {
+                Ok(entry) => entry,
+                Err(e) => {
+                    return Ok(
+                        ValidateCallbackResult::Invalid(
+                            format!("Expected to get Vote from Record: {e:?}"),
+                        ),
+                    );
+                }
+            };
+            validate_update_vote(action, vote, original_action, original_vote, config)
+        }
+        EntryTypes::Channel(channel) => {
+            let original_channel = match Channel::try_from(original_record) {
+                Ok(entry) => entry,
+                Err(e) => {
+                    return Ok(
+                        ValidateCallbackResult::Invalid(
+                            format!("Expected to get Channel from Record: {e:?}"),
+                        ),
+                    );
+                }
+            };
+            validate_update_channel(action, channel, original_action, original_channel, config)
+        }
+        EntryTypes::FileChunk(file_chunk) => {
+            let original_file_chunk = match FileChunk::try_from(original_record) {
+                Ok(entry) => entry,
+                Err(e) => {
+                    return Ok(
+                        ValidateCallbackResult::Invalid(
+                            format!("Expected to get FileChunk from Record: {e:?}"),
+                        ),
+                    );
+                }
+            };
+            validate_update_file_chunk(
+                action,
+                file_chunk,
+                original_action,
+                original_file_chunk,
+                config,
+            )
+        }
+        EntryTypes::FileMetadata(file_metadata) => {
+            let original_file_metadata = match FileMetadata::try_from(original_record) {
+                Ok(entry) => entry,
+                Err(e) => {
+                    return Ok(
+                        ValidateCallbackResult::Invalid(
+                            format!("Expected to get FileMetadata from Record: {e:?}"),
+                        ),
+                    );
+                }
+            };
+            validate_update_file_metadata(
+                action,
+                file_metadata,
+                original_action,
+                original_file_metadata,
+                config,
+            )
+        }
+        EntryTypes::Poll(poll) => {
+            let original_poll = match Poll::try_from(original_record) {
+                Ok(entry) => entry,
+                Err(e) => {
+                    return Ok(
+                        ValidateCallbackResult::Invalid(
+                            format!("Expected to get Poll from Record: {e:?}"),
+                        ),
+                    );
+                }
+            };
+            validate_update_poll(action, poll, original_action, original_poll, config)
+        }
+        EntryTypes::PollVote(poll_vote) => {
+            let original_poll_vote = match PollVote::try_from(original_record) {
+                Ok(entry) => entry,
+                Err(e) => {
+                    return Ok(
+                        ValidateCallbackResult::Invalid(
+                            format!("Expected to get PollVote from Record: {e:?}"),
+                        ),
+                    );
+                }
+            };
+            validate_update_poll_vote(
+                action,
+                poll_vote,
+                original_action,
+                original_poll_vote,
+                config,
+            )
+        }
+        EntryTypes::Bookmark(bookmark) => {
+            let original_bookmark = match Bookmark::try_from(original_record) {
+                Ok(entry) => entry,
+                Err(e) => {
+                    return Ok(
+                        ValidateCallbackResult::Invalid(
+                            format!("Expected to get Bookmark from Record: {e:?}"),
+                        ),
+                    );
+                }
+            };
+            validate_update_bookmark(action, bookmark, original_action, original_bookmark, config)
+        }
+        EntryTypes::Report(report) => {
+            let original_report = match Report::try_from(original_record) {
+                Ok(entry) => entry,
+                Err(e) => {
+                    return Ok(
+                        ValidateCallbackResult::Invalid(
+                            format!("Expected to get Report from Record: {e:?}"),
+                        ),
+                    );
+                }
+            };
+            validate_update_report(action, report, original_action, original_report, config)
+        }
+        EntryTypes::ModerationAction(moderation_action) => {
+            let original_moderation_action = match ModerationAction::try_from(original_record) {
+                Ok(entry) => entry,
+                Err(e) => {
+                    return Ok(
+                        ValidateCallbackResult::Invalid(
+                            format!("Expected to get ModerationAction from Record: {e:?}"),
+                        ),
+                    );
+                }
+            };
+            validate_update_moderation_action(
+                action,
+                moderation_action,
+                original_action,
+                original_moderation_action,
+                config,
+            )
+        }
+    }
+}
+fn validate_delete(action: Delete, config: &ForumConfig) -> ExternResult<ValidateCallbackResult> {
+    let original_record = must_get_valid_record(action.deletes_address.clone())?;
+    let original_action = match EntryCreationAction::try_from(
+        original_record.action().clone(),
+    ) {
+        Ok(action) => action,
+        Err(e) => {
+            return Ok(
+                ValidateCallbackResult::Invalid(
+                    format!("Expected to get EntryCreationAction from Action: {e:?}"),
+                ),
+            );
+        }
+    };
+    let EntryType::App(app_entry_type) = original_action.entry_type() else {
+        return Ok(ValidateCallbackResult::Valid);
+    };
+    let Some(entry) = original_record.entry().as_option() else {
+        if !app_entry_type.visibility().is_public() {
+            // Only the author holds the content of a private entry, so only authorship can be checked
+            if action.author.ne(original_action.author()) {
+                return Ok(
+                    ValidateCallbackResult::Invalid(
+                        "Only the author of a private entry can delete it".to_string(),
+                    ),
+                );
+            }
+            return Ok(ValidateCallbackResult::Valid);
+        }
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Original record for a delete must contain an entry".to_string(),
+            ),
+        );
+    };
+    let Some(original_app_entry) = EntryTypes::deserialize_from_type(
+        app_entry_type.zome_index,
+        app_entry_type.entry_index,
+        entry,
+    )? else {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Original app entry must be one of the defined entry types for this zome"
+                    .to_string(),
+            ),
+        );
+    };
+    match original_app_entry {
+        EntryTypes::Post(original_post) => {
+            validate_delete_post(action, original_action, original_post, config)
+        }
+        EntryTypes::Comment(original_comment) => {
+            validate_delete_comment(action, original_action, original_comment, config)
+        }
+        EntryTypes::Reaction(original_reaction) => {
+            validate_delete_reaction(action, original_action, original_reaction, config)
+        }
+        EntryTypes::Vote(original_vote) => {
+            validate_delete_vote(action, original_action, original_vote, config)
+        }
+        EntryTypes::Channel(original_channel) => {
+            validate_delete_channel(action, original_action, original_channel, config)
+        }
+        EntryTypes::FileChunk(original_file_chunk) => {
+            validate_delete_file_chunk(action, original_action, original_file_chunk, config)
+        }
+        EntryTypes::FileMetadata(original_file_metadata) => {
+            validate_delete_file_metadata(action, original_action, original_file_metadata, config)
+        }
+        EntryTypes::Poll(original_poll) => {
+            validate_delete_poll(action, original_action, original_poll, config)
+        }
+        EntryTypes::PollVote(original_poll_vote) => {
+            validate_delete_poll_vote(action, original_action, original_poll_vote, config)
+        }
+        EntryTypes::Bookmark(original_bookmark) => {
+            validate_delete_bookmark(action, original_action, original_bookmark, config)
+        }
+        EntryTypes::Report(original_report) => {
+            validate_delete_report(action, original_action, original_report, config)
+        }
+        EntryTypes::ModerationAction(original_moderation_action) => {
+            validate_delete_moderation_action(
+                action,
+                original_action,
+                original_moderation_action,
+                config,
+            )
+        }
+    }
+}
+fn validate_create_link(
+    link_type: LinkTypes,
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+    config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    match link_type {
+        LinkTypes::PostUpdates => {
+            validate_create_link_post_updates(action, base_address, target_address, tag, config)
+        }
+        LinkTypes::PostToComments => {
+            validate_create_link_post_to_comments(action, base_address, target_address, tag, config)
+        }
+        LinkTypes::AllPosts => {
+            validate_create_link_all_posts(action, base_address, target_address, tag, config)
+        }
+        LinkTypes::CommentToReplies => {
+            validate_create_link_comment_to_replies(
+                action,
+                base_address,
+                target_address,
+                tag,
+                config,
+            )
+        }
+        LinkTypes::TargetToReactions => {
+            validate_create_link_target_to_reactions(
+                action,
+                base_address,
+                target_address,
+                tag,
+                config,
+            )
+        }
+        LinkTypes::TargetToVotes => {
+            validate_create_link_target_to_votes(action, base_address, target_address, tag, config)
+        }
+        LinkTypes::TagPath => {
+            validate_create_link_tag_path(action, base_address, target_address, tag, config)
+        }
+        LinkTypes::TagToPosts => {
+            validate_create_link_tag_to_posts(action, base_address, target_address, tag, config)
+        }
+        LinkTypes::AllChannels => {
+            validate_create_link_all_channels(action, base_address, target_address, tag, config)
+        }
+        LinkTypes::ChannelToPosts => {
+            validate_create_link_channel_to_posts(
+                action,
+                base_address,
+                target_address,
+                tag,
+                config,
+            )
+        }
+        LinkTypes::AgentToMentions => {
+            validate_create_link_agent_to_mentions(
+                action,
+                base_address,
+                target_address,
+                tag,
+                config,
+            )
+        }
+        LinkTypes::PostToAttachments => {
+            validate_create_link_post_to_attachments(
+                action,
+                base_address,
+                target_address,
+                tag,
+                config,
+            )
+        }
+        LinkTypes::PostToPolls => {
+            validate_create_link_post_to_polls(action, base_address, target_address, tag, config)
+        }
+        LinkTypes::PollToVotes => {
+            validate_create_link_poll_to_votes(action, base_address, target_address, tag, config)
+        }
+        LinkTypes::ModerationQueue => {
+            validate_create_link_moderation_queue(
+                action,
+                base_address,
+                target_address,
+                tag,
+                config,
+            )
+        }
+        LinkTypes::TargetToModerationActions => {
+            validate_create_link_target_to_moderation_actions(
+                action,
+                base_address,
+                target_address,
+                tag,
+                config,
+            )
+        }
+        LinkTypes::PinnedPosts => {
+            validate_create_link_pinned_posts(action, base_address, target_address, tag, config)
+        }
+        LinkTypes::RestoredFrom => {
+            validate_create_link_restored_from(action, base_address, target_address, tag, config)
+        }
+        LinkTypes::RestoredAs => {
+            validate_create_link_restored_as(action, base_address, target_address, tag, config)
+        }
+        LinkTypes::PostToChannel => {
+            validate_create_link_post_to_channel(action, base_address, target_address, tag, config)
+        }
+    }
+}
+fn validate_delete_link(
+    link_type: LinkTypes,
+    action: DeleteLink,
+    original_action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+    config: &ForumConfig,
 ) -> ExternResult<ValidateCallbackResult> {
-    Ok(ValidateCallbackResult::Valid)
+    match link_type {
+        LinkTypes::PostUpdates => {
+            validate_delete_link_post_updates(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+                config,
+            )
+        }
+        LinkTypes::PostToComments => {
+            validate_delete_link_post_to_comments(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+                config,
+            )
+        }
+        LinkTypes::AllPosts => {
+            validate_delete_link_all_posts(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+                config,
+            )
+        }
+        LinkTypes::CommentToReplies => {
+            validate_delete_link_comment_to_replies(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+                config,
+            )
+        }
+        LinkTypes::TargetToReactions => {
+            validate_delete_link_target_to_reactions(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+                config,
+            )
+        }
+        LinkTypes::TargetToVotes => {
+            validate_delete_link_target_to_votes(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+                config,
+            )
+        }
+        LinkTypes::TagPath => {
+            validate_delete_link_tag_path(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+                config,
+            )
+        }
+        LinkTypes::TagToPosts => {
+            validate_delete_link_tag_to_posts(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+                config,
+            )
+        }
+        LinkTypes::AllChannels => {
+            validate_delete_link_all_channels(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+                config,
+            )
+        }
+        LinkTypes::ChannelToPosts => {
+            validate_delete_link_channel_to_posts(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+                config,
+            )
+        }
+        LinkTypes::AgentToMentions => {
+            validate_delete_link_agent_to_mentions(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+                config,
+            )
+        }
+        LinkTypes::PostToAttachments => {
+            validate_delete_link_post_to_attachments(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+                config,
+            )
+        }
+        LinkTypes::PostToPolls => {
+            validate_delete_link_post_to_polls(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+                config,
+            )
+        }
+        LinkTypes::PollToVotes => {
+            validate_delete_link_poll_to_votes(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+                config,
+            )
+        }
+        LinkTypes::ModerationQueue => {
+            validate_delete_link_moderation_queue(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+                config,
+            )
+        }
+        LinkTypes::TargetToModerationActions => {
+            validate_delete_link_target_to_moderation_actions(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+                config,
+            )
+        }
+        LinkTypes::PinnedPosts => {
+            validate_delete_link_pinned_posts(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+                config,
+            )
+        }
+        LinkTypes::RestoredFrom => {
+            validate_delete_link_restored_from(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+                config,
+            )
+        }
+        LinkTypes::RestoredAs => {
+            validate_delete_link_restored_as(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+                config,
+            )
+        }
+        LinkTypes::PostToChannel => {
+            validate_delete_link_post_to_channel(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+                config,
+            )
+        }
+    }
 }
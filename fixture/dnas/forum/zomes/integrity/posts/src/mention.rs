@@ -0,0 +1,101 @@
+use hdi::prelude::*;
+use crate::{ForumConfig, UnitEntryTypes};
+/// Length of an agent public key in its `u`-prefixed base64 encoding.
+const AGENT_PUB_KEY_LENGTH: usize = 53;
+/// The agents mentioned in the text, in order of first mention.
+///
+/// A mention is an `@` directly followed by the agent's public key in its usual `uhCAk...`
+/// encoding.
+pub fn extract_mentions(text: &str) -> Vec<AgentPubKey> {
+    let mut mentions: Vec<AgentPubKey> = Vec::new();
+    for (index, _) in text.match_indices('@') {
+        let Some(candidate) = text.get(index + 1..index + 1 + AGENT_PUB_KEY_LENGTH) else {
+            continue;
+        };
+        let Ok(agent) = AgentPubKey::try_from(candidate) else {
+            continue;
+        };
+        if !mentions.contains(&agent) {
+            mentions.push(agent);
+        }
+    }
+    mentions
+}
+/// The text of a Post or a Comment record where mentions are looked for.
+pub fn mentionable_text(record: &Record) -> ExternResult<Option<String>> {
+    if crate::has_entry_type(record.action(), UnitEntryTypes::Post)? {
+        let post = crate::Post::try_from(record.clone())?;
+        return Ok(Some(post.content));
+    }
+    if crate::has_entry_type(record.action(), UnitEntryTypes::Comment)? {
+        let comment = crate::Comment::try_from(record.clone())?;
+        return Ok(Some(comment.comment));
+    }
+    Ok(None)
+}
+pub fn validate_create_link_agent_to_mentions(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+    _config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    let Some(mentioned_agent) = base_address.into_agent_pub_key() else {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("AgentToMentions links must have the mentioned agent as their base"),
+            ),
+        );
+    };
+    let action_hash = target_address
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("No action hash associated with link"))
+            ),
+        )?;
+    let record = must_get_valid_record(action_hash)?;
+    let Some(text) = mentionable_text(&record)? else {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("AgentToMentions links must point to a Post or a Comment"),
+            ),
+        );
+    };
+    if !extract_mentions(&text).contains(&mentioned_agent) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("The linked entry does not mention the agent"),
+            ),
+        );
+    }
+    if action.author.ne(record.action().author()) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only the author of an entry can notify the agents it mentions"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_delete_link_agent_to_mentions(
+    action: DeleteLink,
+    original_action: CreateLink,
+    base: AnyLinkableHash,
+    target: AnyLinkableHash,
+    _tag: LinkTag,
+    config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    if base.eq(&AnyLinkableHash::from(action.author.clone())) {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    if crate::is_author_of_commented_post_for(&action.author, target)? {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    crate::validate_author_or_moderator(
+        config,
+        &action.author,
+        &original_action.author,
+        "Only the link author, the mentioned agent, the author of the commented Post or a moderator can delete this link",
+    )
+}
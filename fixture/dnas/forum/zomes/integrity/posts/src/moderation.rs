@@ -0,0 +1,281 @@
+use hdi::prelude::*;
+use crate::{ForumConfig, UnitEntryTypes};
+/// The path under which open reports wait for a moderator.
+pub const MODERATION_QUEUE_ANCHOR: &str = "moderation_queue";
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportReason {
+    Spam,
+    Harassment,
+    OffTopic,
+    Illegal,
+    Other,
+}
+/// A flag raised by an agent on a post or a comment, for moderators to review.
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct Report {
+    pub target_hash: ActionHash,
+    pub reason: ReportReason,
+    pub details: String,
+}
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModerationActionKind {
+    Hide,
+    Restore,
+    Warn,
+}
+impl ModerationActionKind {
+    /// The tag of the TargetToModerationActions link pointing to an action of this kind.
+    pub fn link_tag(&self) -> LinkTag {
+        match self {
+            ModerationActionKind::Hide => LinkTag::new("hide"),
+            ModerationActionKind::Restore => LinkTag::new("restore"),
+            ModerationActionKind::Warn => LinkTag::new("warn"),
+        }
+    }
+    pub fn from_link_tag(tag: &LinkTag) -> Option<ModerationActionKind> {
+        match tag.0.as_slice() {
+            b"hide" => Some(ModerationActionKind::Hide),
+            b"restore" => Some(ModerationActionKind::Restore),
+            b"warn" => Some(ModerationActionKind::Warn),
+            _ => None,
+        }
+    }
+}
+/// A moderator's decision on a post or a comment, optionally in response to a report.
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct ModerationAction {
+    pub target_hash: ActionHash,
+    pub kind: ModerationActionKind,
+    pub reason: String,
+    pub report_hash: Option<ActionHash>,
+}
+fn validate_moderation_target(target_hash: ActionHash) -> ExternResult<ValidateCallbackResult> {
+    let record = must_get_valid_record(target_hash)?;
+    if !crate::has_entry_type(record.action(), UnitEntryTypes::Post)?
+        && !crate::has_entry_type(record.action(), UnitEntryTypes::Comment)?
+    {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only Posts and Comments can be reported and moderated"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+fn validate_moderation_text(text: &str, config: &ForumConfig) -> ValidateCallbackResult {
+    if text.chars().count() > config.max_comment_length {
+        return ValidateCallbackResult::Invalid(
+            format!(
+                "Report and moderation texts cannot be longer than {} characters",
+                config.max_comment_length,
+            ),
+        );
+    }
+    if crate::contains_control_characters(text, true) {
+        return ValidateCallbackResult::Invalid(
+            String::from("Report and moderation texts cannot contain control characters"),
+        );
+    }
+    ValidateCallbackResult::Valid
+}
+pub fn validate_create_report(
+    _action: EntryCreationAction,
+    report: Report,
+    config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    let text_result = validate_moderation_text(&report.details, config);
+    if text_result != ValidateCallbackResult::Valid {
+        return Ok(text_result);
+    }
+    validate_moderation_target(report.target_hash)
+}
+pub fn validate_update_report(
+    _action: Update,
+    _report: Report,
+    _original_action: EntryCreationAction,
+    _original_report: Report,
+    _config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from("Reports cannot be updated")))
+}
+pub fn validate_delete_report(
+    action: Delete,
+    original_action: EntryCreationAction,
+    _original_report: Report,
+    config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    crate::validate_author_or_moderator(
+        config,
+        &action.author,
+        original_action.author(),
+        "Only the author of a Report or a moderator can delete it",
+    )
+}
+pub fn validate_create_moderation_action(
+    action: EntryCreationAction,
+    moderation_action: ModerationAction,
+    config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    if !config.is_moderator(action.author()) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only moderators can take moderation actions"),
+            ),
+        );
+    }
+    let text_result = validate_moderation_text(&moderation_action.reason, config);
+    if text_result != ValidateCallbackResult::Valid {
+        return Ok(text_result);
+    }
+    if let Some(report_hash) = moderation_action.report_hash {
+        let report = Report::try_from(must_get_valid_record(report_hash)?)?;
+        if report.target_hash.ne(&moderation_action.target_hash) {
+            return Ok(
+                ValidateCallbackResult::Invalid(
+                    String::from("A moderation action must target what its Report targets"),
+                ),
+            );
+        }
+    }
+    validate_moderation_target(moderation_action.target_hash)
+}
+pub fn validate_update_moderation_action(
+    _action: Update,
+    _moderation_action: ModerationAction,
+    _original_action: EntryCreationAction,
+    _original_moderation_action: ModerationAction,
+    _config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from("Moderation actions cannot be updated")))
+}
+pub fn validate_delete_moderation_action(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_moderation_action: ModerationAction,
+    _config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(
+        ValidateCallbackResult::Invalid(
+            String::from("Moderation actions cannot be deleted, take a new one instead"),
+        ),
+    )
+}
+pub fn validate_create_link_moderation_queue(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+    _config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    let queue_hash = Path::from(MODERATION_QUEUE_ANCHOR).path_entry_hash()?;
+    if base_address.ne(&AnyLinkableHash::from(queue_hash)) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("ModerationQueue links must have the moderation_queue path as their base"),
+            ),
+        );
+    }
+    let action_hash = target_address
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("No action hash associated with link"))
+            ),
+        )?;
+    let record = must_get_valid_record(action_hash)?;
+    let _report: crate::Report = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("Linked action must reference an entry"))
+            ),
+        )?;
+    if action.author.ne(record.action().author()) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only the author of a Report can add it to the moderation queue"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_delete_link_moderation_queue(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+    config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    crate::validate_author_or_moderator(
+        config,
+        &action.author,
+        &original_action.author,
+        "Only the author of a Report or a moderator can remove it from the moderation queue",
+    )
+}
+pub fn validate_create_link_target_to_moderation_actions(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+    _config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    let action_hash = target_address
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("No action hash associated with link"))
+            ),
+        )?;
+    let record = must_get_valid_record(action_hash)?;
+    let moderation_action: crate::ModerationAction = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("Linked action must reference an entry"))
+            ),
+        )?;
+    if base_address.ne(&AnyLinkableHash::from(moderation_action.target_hash)) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("TargetToModerationActions links must have the moderated target as their base"),
+            ),
+        );
+    }
+    if tag.ne(&moderation_action.kind.link_tag()) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("TargetToModerationActions links must be tagged with the kind of action"),
+            ),
+        );
+    }
+    if action.author.ne(record.action().author()) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only the author of a moderation action can link it to its target"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_delete_link_target_to_moderation_actions(
+    _action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+    _config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(
+        ValidateCallbackResult::Invalid(
+            String::from("TargetToModerationActions links cannot be deleted"),
+        ),
+    )
+}
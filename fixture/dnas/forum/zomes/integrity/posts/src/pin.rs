@@ -0,0 +1,65 @@
+use hdi::prelude::*;
+use crate::ForumConfig;
+/// The path pinned posts are linked from.
+pub const PINNED_POSTS_ANCHOR: &str = "pinned_posts";
+pub fn validate_create_link_pinned_posts(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+    config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    if !config.is_moderator(&action.author) {
+        return Ok(
+            ValidateCallbackResult::Invalid(String::from("Only moderators can pin posts")),
+        );
+    }
+    let pinned_posts_hash = Path::from(PINNED_POSTS_ANCHOR).path_entry_hash()?;
+    if base_address.ne(&AnyLinkableHash::from(pinned_posts_hash)) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("PinnedPosts links must have the pinned_posts path as their base"),
+            ),
+        );
+    }
+    let action_hash = target_address
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("No action hash associated with link"))
+            ),
+        )?;
+    let record = must_get_valid_record(action_hash)?;
+    let _post: crate::Post = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("Linked action must reference an entry"))
+            ),
+        )?;
+    if !matches!(record.action(), Action::Create(_)) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("PinnedPosts links must point to the original Create of a Post"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_delete_link_pinned_posts(
+    action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+    config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    if !config.is_moderator(&action.author) {
+        return Ok(
+            ValidateCallbackResult::Invalid(String::from("Only moderators can unpin posts")),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
@@ -0,0 +1,312 @@
+use hdi::prelude::*;
+use crate::{ForumConfig, UnitEntryTypes};
+pub const MAX_POLL_OPTION_LENGTH: usize = 200;
+/// A question asked in a Post, that agents answer by picking among its options.
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct Poll {
+    pub post_hash: ActionHash,
+    pub question: String,
+    pub options: Vec<String>,
+    /// Votes dated at or after this time are rejected.
+    pub closes_at: Timestamp,
+    /// Whether voters may pick more than one option.
+    pub multiple_choice: bool,
+}
+/// An agent's answer to a Poll, as indices into its options.
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct PollVote {
+    pub poll_hash: ActionHash,
+    pub option_indices: Vec<usize>,
+}
+pub fn validate_create_poll(
+    action: EntryCreationAction,
+    poll: Poll,
+    config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    if poll.question.trim().is_empty() {
+        return Ok(ValidateCallbackResult::Invalid(String::from("Poll question cannot be empty")));
+    }
+    if poll.question.chars().count() > config.max_title_length {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                format!(
+                    "Poll question cannot be longer than {} characters",
+                    config.max_title_length,
+                ),
+            ),
+        );
+    }
+    if poll.options.len() < 2 || poll.options.len() > config.max_poll_options {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                format!("A Poll must have between 2 and {} options", config.max_poll_options),
+            ),
+        );
+    }
+    for (i, option) in poll.options.iter().enumerate() {
+        if option.trim().is_empty() {
+            return Ok(
+                ValidateCallbackResult::Invalid(String::from("Poll options cannot be empty")),
+            );
+        }
+        if option.chars().count() > MAX_POLL_OPTION_LENGTH {
+            return Ok(
+                ValidateCallbackResult::Invalid(
+                    format!(
+                        "Poll options cannot be longer than {MAX_POLL_OPTION_LENGTH} characters"
+                    ),
+                ),
+            );
+        }
+        if poll.options[..i].contains(option) {
+            return Ok(
+                ValidateCallbackResult::Invalid(format!("Poll option {option:?} is repeated")),
+            );
+        }
+    }
+    if &poll.closes_at <= action.timestamp() {
+        return Ok(
+            ValidateCallbackResult::Invalid(String::from("A Poll cannot be closed when created")),
+        );
+    }
+    let post_record = must_get_valid_record(poll.post_hash)?;
+    if !crate::has_entry_type(post_record.action(), UnitEntryTypes::Post)? {
+        return Ok(
+            ValidateCallbackResult::Invalid(String::from("Polls must belong to a Post")),
+        );
+    }
+    if action.author().ne(post_record.action().author()) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only the author of a Post can add a Poll to it"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_update_poll(
+    _action: Update,
+    _poll: Poll,
+    _original_action: EntryCreationAction,
+    _original_poll: Poll,
+    _config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from("Polls cannot be updated")))
+}
+pub fn validate_delete_poll(
+    action: Delete,
+    original_action: EntryCreationAction,
+    _original_poll: Poll,
+    config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    crate::validate_author_or_moderator(
+        config,
+        &action.author,
+        original_action.author(),
+        "Only the author of a Poll or a moderator can delete it",
+    )
+}
+pub fn validate_create_poll_vote(
+    action: EntryCreationAction,
+    poll_vote: PollVote,
+    _config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    let poll_record = must_get_valid_record(poll_vote.poll_hash.clone())?;
+    let poll_timestamp = poll_record.action().timestamp();
+    let poll = Poll::try_from(poll_record)?;
+    if action.timestamp() < &poll_timestamp {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("A PollVote cannot be dated before its Poll"),
+            ),
+        );
+    }
+    if action.timestamp() >= &poll.closes_at {
+        return Ok(ValidateCallbackResult::Invalid(String::from("The Poll is closed")));
+    }
+    if poll_vote.option_indices.is_empty() {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("A PollVote must pick at least one option"),
+            ),
+        );
+    }
+    if !poll.multiple_choice && poll_vote.option_indices.len() > 1 {
+        return Ok(
+            ValidateCallbackResult::Invalid(String::from("This Poll only allows a single choice")),
+        );
+    }
+    for (i, option_index) in poll_vote.option_indices.iter().enumerate() {
+        if *option_index >= poll.options.len() {
+            return Ok(
+                ValidateCallbackResult::Invalid(
+                    format!("The Poll has no option number {option_index}"),
+                ),
+            );
+        }
+        if poll_vote.option_indices[..i].contains(option_index) {
+            return Ok(
+                ValidateCallbackResult::Invalid(
+                    format!("Option number {option_index} is picked more than once"),
+                ),
+            );
+        }
+    }
+    if let EntryCreationAction::Create(create) = action {
+        let previous_votes = crate::must_get_active_creates_of_type(
+            &create.author,
+            create.prev_action,
+            poll_timestamp,
+            UnitEntryTypes::PollVote,
+        )?;
+        for vote_hash in previous_votes {
+            let previous_vote = PollVote::try_from(must_get_valid_record(vote_hash)?)?;
+            if previous_vote.poll_hash.eq(&poll_vote.poll_hash) {
+                return Ok(
+                    ValidateCallbackResult::Invalid(
+                        String::from("An agent can only vote once on each Poll"),
+                    ),
+                );
+            }
+        }
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_update_poll_vote(
+    _action: Update,
+    _poll_vote: PollVote,
+    _original_action: EntryCreationAction,
+    _original_poll_vote: PollVote,
+    _config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from("PollVotes cannot be updated")))
+}
+pub fn validate_delete_poll_vote(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_poll_vote: PollVote,
+    _config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from("PollVotes cannot be deleted")))
+}
+pub fn validate_create_link_post_to_polls(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+    _config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    let post_hash = base_address
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("No action hash associated with link"))
+            ),
+        )?;
+    let action_hash = target_address
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("No action hash associated with link"))
+            ),
+        )?;
+    let record = must_get_valid_record(action_hash)?;
+    let poll: crate::Poll = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("Linked action must reference an entry"))
+            ),
+        )?;
+    if poll.post_hash.ne(&post_hash) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("PostToPolls links must have the Poll's Post as their base"),
+            ),
+        );
+    }
+    if action.author.ne(record.action().author()) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only the author of a Poll can link it to its Post"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_delete_link_post_to_polls(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+    config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    crate::validate_author_or_moderator(
+        config,
+        &action.author,
+        &original_action.author,
+        "Only the author of a PostToPolls link or a moderator can delete it",
+    )
+}
+pub fn validate_create_link_poll_to_votes(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+    _config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    let poll_hash = base_address
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("No action hash associated with link"))
+            ),
+        )?;
+    let action_hash = target_address
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("No action hash associated with link"))
+            ),
+        )?;
+    let record = must_get_valid_record(action_hash)?;
+    let poll_vote: crate::PollVote = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("Linked action must reference an entry"))
+            ),
+        )?;
+    if poll_vote.poll_hash.ne(&poll_hash) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("PollToVotes links must have the voted Poll as their base"),
+            ),
+        );
+    }
+    if action.author.ne(record.action().author()) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only the author of a PollVote can link it to its Poll"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_delete_link_poll_to_votes(
+    _action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+    _config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from("PollToVotes links cannot be deleted")))
+}
@@ -1,45 +1,182 @@
 use hdi::prelude::*;
+use crate::ForumConfig;
+/// The path under which every post is indexed.
+pub const ALL_POSTS_ANCHOR: &str = "all_posts";
 #[hdk_entry_helper]
 #[derive(Clone, PartialEq)]
 pub struct Post {
     pub title: String,
     pub content: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// The channel the post was filed under, `None` for the general forum.
+    #[serde(default)]
+    pub channel_hash: Option<ActionHash>,
 }
 pub fn validate_create_post(
-    _action: EntryCreationAction,
-    _post: Post,
+    action: EntryCreationAction,
+    post: Post,
+    config: &ForumConfig,
 ) -> ExternResult<ValidateCallbackResult> {
+    if post.title.trim().is_empty() {
+        return Ok(ValidateCallbackResult::Invalid(String::from("Post title cannot be empty")));
+    }
+    if post.title.chars().count() > config.max_title_length {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                format!("Post title cannot be longer than {} characters", config.max_title_length),
+            ),
+        );
+    }
+    if crate::contains_control_characters(&post.title, false) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Post title cannot contain control characters"),
+            ),
+        );
+    }
+    if post.content.chars().count() > config.max_content_length {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                format!("Post content cannot be longer than {} characters", config.max_content_length),
+            ),
+        );
+    }
+    if crate::contains_control_characters(&post.content, true) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Post content cannot contain control characters"),
+            ),
+        );
+    }
+    if let Some(channel_hash) = post.channel_hash.clone() {
+        crate::must_get_channel_record(channel_hash)?;
+    }
+    let tags_result = crate::validate_tags(&post.tags, config);
+    if tags_result != ValidateCallbackResult::Valid {
+        return Ok(tags_result);
+    }
+    if let (EntryCreationAction::Create(create), Some(rate_limit)) = (
+        &action,
+        &config.rate_limit,
+    ) {
+        let recent_posts = crate::count_recent_creates(
+            create,
+            crate::UnitEntryTypes::Post,
+            rate_limit.window(),
+        )?;
+        if recent_posts >= rate_limit.max_posts {
+            return Ok(
+                ValidateCallbackResult::Invalid(
+                    format!(
+                        "Cannot create more than {} posts every {} seconds",
+                        rate_limit.max_posts,
+                        rate_limit.window_seconds,
+                    ),
+                ),
+            );
+        }
+    }
     Ok(ValidateCallbackResult::Valid)
 }
 pub fn validate_update_post(
-    _action: Update,
-    _post: Post,
-    _original_action: EntryCreationAction,
-    _original_post: Post,
+    action: Update,
+    post: Post,
+    original_action: EntryCreationAction,
+    original_post: Post,
+    _config: &ForumConfig,
 ) -> ExternResult<ValidateCallbackResult> {
+    if post.channel_hash.ne(&original_post.channel_hash) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("The channel of a Post cannot be changed by updating it, channel moderators move posts instead"),
+            ),
+        );
+    }
+    if &action.timestamp < original_action.timestamp() {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("An update cannot be dated before the Post it updates"),
+            ),
+        );
+    }
+    let original_create = must_get_original_create(original_action)?;
+    if action.author != original_create.author {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only the author of a Post can update it"),
+            ),
+        );
+    }
     Ok(ValidateCallbackResult::Valid)
 }
 pub fn validate_delete_post(
-    _action: Delete,
-    _original_action: EntryCreationAction,
+    action: Delete,
+    original_action: EntryCreationAction,
     _original_post: Post,
+    config: &ForumConfig,
 ) -> ExternResult<ValidateCallbackResult> {
-    Ok(ValidateCallbackResult::Valid)
+    if &action.timestamp < original_action.timestamp() {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("A delete cannot be dated before the Post it deletes"),
+            ),
+        );
+    }
+    let original_create = must_get_original_create(original_action)?;
+    crate::validate_author_or_moderator(
+        config,
+        &action.author,
+        &original_create.author,
+        "Only the author of a Post or a moderator can delete it",
+    )
+}
+/// The edit summary held by a PostUpdates link tag, `None` when the tag is empty.
+pub fn edit_summary_from_link_tag(tag: &LinkTag) -> Option<String> {
+    if tag.0.is_empty() {
+        return None;
+    }
+    String::from_utf8(tag.0.clone()).ok()
 }
 pub fn validate_create_link_post_updates(
-    _action: CreateLink,
+    action: CreateLink,
     base_address: AnyLinkableHash,
     target_address: AnyLinkableHash,
-    _tag: LinkTag,
+    tag: LinkTag,
+    config: &ForumConfig,
 ) -> ExternResult<ValidateCallbackResult> {
-    let action_hash = base_address
+    let Ok(edit_summary) = String::from_utf8(tag.into_inner()) else {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("PostUpdates links must have the edit summary as their tag"),
+            ),
+        );
+    };
+    if edit_summary.chars().count() > config.max_edit_summary_length {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                format!(
+                    "Edit summaries cannot be longer than {} characters",
+                    config.max_edit_summary_length,
+                ),
+            ),
+        );
+    }
+    if crate::contains_control_characters(&edit_summary, false) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Edit summaries cannot contain control characters"),
+            ),
+        );
+    }
+    let original_post_hash = base_address
         .into_action_hash()
         .ok_or(
             wasm_error!(
                 WasmErrorInner::Guest(String::from("No action hash associated with link"))
             ),
         )?;
-    let record = must_get_valid_record(action_hash)?;
+    let record = must_get_valid_record(original_post_hash.clone())?;
     let _post: crate::Post = record
         .entry()
         .to_app_option()
@@ -66,6 +203,28 @@ pub fn validate_create_link_post_updates(
                 WasmErrorInner::Guest(String::from("Linked action must reference an entry"))
             ),
         )?;
+    let Action::Update(update) = record.action() else {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("PostUpdates links must point to an Update of the Post"),
+            ),
+        );
+    };
+    let (root_hash, _) = must_get_original_create_for_update(update)?;
+    if root_hash.ne(&original_post_hash) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("The updated Post is not a revision of the base Post"),
+            ),
+        );
+    }
+    if action.author.ne(&update.author) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only the author of an update can link it as a revision"),
+            ),
+        );
+    }
     Ok(ValidateCallbackResult::Valid)
 }
 pub fn validate_delete_link_post_updates(
@@ -74,6 +233,7 @@ pub fn validate_delete_link_post_updates(
     _base: AnyLinkableHash,
     _target: AnyLinkableHash,
     _tag: LinkTag,
+    _config: &ForumConfig,
 ) -> ExternResult<ValidateCallbackResult> {
     Ok(
         ValidateCallbackResult::Invalid(
@@ -82,11 +242,25 @@ pub fn validate_delete_link_post_updates(
     )
 }
 pub fn validate_create_link_all_posts(
-    _action: CreateLink,
-    _base_address: AnyLinkableHash,
+    action: CreateLink,
+    base_address: AnyLinkableHash,
     target_address: AnyLinkableHash,
-    _tag: LinkTag,
+    tag: LinkTag,
+    _config: &ForumConfig,
 ) -> ExternResult<ValidateCallbackResult> {
+    let all_posts_hash = Path::from(ALL_POSTS_ANCHOR).path_entry_hash()?;
+    if base_address.ne(&AnyLinkableHash::from(all_posts_hash)) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("AllPosts links must have the all_posts path as their base"),
+            ),
+        );
+    }
+    if !tag.0.is_empty() {
+        return Ok(
+            ValidateCallbackResult::Invalid(String::from("AllPosts links must have an empty tag")),
+        );
+    }
     // Check the entry type for the given action hash
     let action_hash = target_address
         .into_action_hash()
@@ -105,16 +279,69 @@ pub fn validate_create_link_all_posts(
                 WasmErrorInner::Guest(String::from("Linked action must reference an entry"))
             ),
         )?;
-    // TODO: add the appropriate validation rules
+    let Action::Create(create) = record.action() else {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("AllPosts links must point to the original Create of a Post"),
+            ),
+        );
+    };
+    if action.author.ne(&create.author) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only the author of a Post can add it to AllPosts"),
+            ),
+        );
+    }
     Ok(ValidateCallbackResult::Valid)
 }
 pub fn validate_delete_link_all_posts(
-    _action: DeleteLink,
-    _original_action: CreateLink,
+    action: DeleteLink,
+    original_action: CreateLink,
     _base: AnyLinkableHash,
     _target: AnyLinkableHash,
     _tag: LinkTag,
+    _config: &ForumConfig,
 ) -> ExternResult<ValidateCallbackResult> {
-    // TODO: add the appropriate validation rules
+    if action.author.ne(&original_action.author) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only the author of an AllPosts link can delete it"),
+            ),
+        );
+    }
     Ok(ValidateCallbackResult::Valid)
 }
+/// Follows the update chain of the given action back to the `Create` that started it.
+pub fn must_get_original_create(action: EntryCreationAction) -> ExternResult<Create> {
+    match action {
+        EntryCreationAction::Create(create) => Ok(create),
+        EntryCreationAction::Update(update) => {
+            let (_, create) = must_get_original_create_for_update(&update)?;
+            Ok(create)
+        }
+    }
+}
+/// Follows the update chain of the given update back to the `Create` that started it,
+/// returning that `Create` along with its hash.
+pub fn must_get_original_create_for_update(
+    update: &Update,
+) -> ExternResult<(ActionHash, Create)> {
+    let mut action_hash = update.original_action_address.clone();
+    loop {
+        let action = must_get_action(action_hash.clone())?;
+        match action.action() {
+            Action::Create(create) => return Ok((action_hash, create.clone())),
+            Action::Update(update) => {
+                action_hash = update.original_action_address.clone();
+            }
+            _ => {
+                return Err(
+                    wasm_error!(
+                        WasmErrorInner::Guest(String::from("Updated action must be a Create or an Update"))
+                    ),
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use hdi::prelude::*;
+use crate::{ForumConfig, UnitEntryTypes};
+/// A short reaction code (e.g. "heart") left by an agent on a post or a comment.
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct Reaction {
+    pub code: String,
+    pub target_hash: ActionHash,
+}
+pub fn validate_create_reaction(
+    action: EntryCreationAction,
+    reaction: Reaction,
+    config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    if !config.allowed_reactions.contains(&reaction.code) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                format!("Reaction code {:?} is not allowed in this forum", reaction.code),
+            ),
+        );
+    }
+    let record = must_get_valid_record(reaction.target_hash.clone())?;
+    if !crate::has_entry_type(record.action(), UnitEntryTypes::Post)?
+        && !crate::has_entry_type(record.action(), UnitEntryTypes::Comment)?
+    {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Reactions must target a Post or a Comment"),
+            ),
+        );
+    }
+    let target_timestamp = record.action().timestamp();
+    if action.timestamp() < &target_timestamp {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("A Reaction cannot be dated before its target"),
+            ),
+        );
+    }
+    if let EntryCreationAction::Create(create) = action {
+        let active_reactions = crate::must_get_active_creates_of_type(
+            &create.author,
+            create.prev_action,
+            target_timestamp,
+            UnitEntryTypes::Reaction,
+        )?;
+        for reaction_hash in active_reactions {
+            let existing_reaction = Reaction::try_from(must_get_valid_record(reaction_hash)?)?;
+            if existing_reaction.target_hash.eq(&reaction.target_hash) {
+                return Ok(
+                    ValidateCallbackResult::Invalid(
+                        String::from("An agent can only have one reaction on each target"),
+                    ),
+                );
+            }
+        }
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_update_reaction(
+    _action: Update,
+    _reaction: Reaction,
+    _original_action: EntryCreationAction,
+    _original_reaction: Reaction,
+    _config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from("Reactions cannot be updated")))
+}
+pub fn validate_delete_reaction(
+    action: Delete,
+    original_action: EntryCreationAction,
+    _original_reaction: Reaction,
+    _config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author.ne(original_action.author()) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only the author of a Reaction can delete it"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_create_link_target_to_reactions(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+    _config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    let action_hash = target_address
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("No action hash associated with link"))
+            ),
+        )?;
+    let record = must_get_valid_record(action_hash)?;
+    let reaction: crate::Reaction = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("Linked action must reference an entry"))
+            ),
+        )?;
+    if base_address.ne(&AnyLinkableHash::from(reaction.target_hash)) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("TargetToReactions links must have the reaction's target as their base"),
+            ),
+        );
+    }
+    if tag.0.ne(reaction.code.as_bytes()) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("TargetToReactions links must have the reaction code as their tag"),
+            ),
+        );
+    }
+    if action.author.ne(record.action().author()) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only the author of a Reaction can link it to its target"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_delete_link_target_to_reactions(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+    _config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author.ne(&original_action.author) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only the author of a TargetToReactions link can delete it"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
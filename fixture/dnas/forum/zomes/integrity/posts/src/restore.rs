@@ -0,0 +1,248 @@
+use hdi::prelude::*;
+use crate::{ForumConfig, LinkTypes, UnitEntryTypes};
+/// The RestoredFrom links leading from a restored Post or Comment back to the deleted one it
+/// replaces, newest restoration first. A restore re-links the comments and replies of the
+/// deleted original to its copy, and those links carry this proof as their tag.
+#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone, PartialEq)]
+pub struct RestorationProof(pub Vec<ActionHash>);
+/// The tag of the restoration links of a Post: the hash of the revision that was restored, or
+/// nothing when the original itself was restored.
+pub fn restored_revision_from_link_tag(tag: &LinkTag) -> Option<ActionHash> {
+    if tag.0.is_empty() {
+        return None;
+    }
+    ActionHash::try_from_raw_39(tag.0.clone()).ok()
+}
+/// Checks that the given Post is identical to the deleted original or to one of its revisions,
+/// as named by the tag of the restoration link.
+fn validate_restored_post(
+    original_hash: ActionHash,
+    original_record: Record,
+    restored_record: Record,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let revision_record = if tag.0.is_empty() {
+        original_record
+    } else {
+        let Some(revision_hash) = restored_revision_from_link_tag(&tag) else {
+            return Ok(
+                ValidateCallbackResult::Invalid(
+                    String::from("The restoration links of a Post must have the restored revision as their tag"),
+                ),
+            );
+        };
+        let revision_record = must_get_valid_record(revision_hash)?;
+        let Action::Update(update) = revision_record.action() else {
+            return Ok(
+                ValidateCallbackResult::Invalid(
+                    String::from("The restored revision is not a revision of the deleted Post"),
+                ),
+            );
+        };
+        let (root_hash, _) = crate::must_get_original_create_for_update(update)?;
+        if root_hash.ne(&original_hash) {
+            return Ok(
+                ValidateCallbackResult::Invalid(
+                    String::from("The restored revision is not a revision of the deleted Post"),
+                ),
+            );
+        }
+        revision_record
+    };
+    let revision = crate::Post::try_from(revision_record)?;
+    let restored_post = crate::Post::try_from(restored_record)?;
+    if revision.ne(&restored_post) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("A restored Post must be identical to the deleted one or to its restored revision"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+/// Checks that `restored_hash` is a valid restoration of `original_hash` made by `author`:
+/// both must be the `Create` of a Post or of a Comment, the copy must be identical to what was
+/// deleted and authored by whoever restores it, and only the original author or a moderator
+/// may restore.
+fn validate_restoration(
+    author: &AgentPubKey,
+    original_hash: AnyLinkableHash,
+    restored_hash: AnyLinkableHash,
+    tag: LinkTag,
+    config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    let original_hash = original_hash
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("No action hash associated with link"))
+            ),
+        )?;
+    let restored_hash = restored_hash
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("No action hash associated with link"))
+            ),
+        )?;
+    let original_record = must_get_valid_record(original_hash.clone())?;
+    let restored_record = must_get_valid_record(restored_hash)?;
+    let (Action::Create(original_create), Action::Create(restored_create)) = (
+        original_record.action().clone(),
+        restored_record.action().clone(),
+    ) else {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only the original Create of a Post or Comment can be restored"),
+            ),
+        );
+    };
+    if crate::has_entry_type(original_record.action(), UnitEntryTypes::Post)? {
+        if !crate::has_entry_type(restored_record.action(), UnitEntryTypes::Post)? {
+            return Ok(
+                ValidateCallbackResult::Invalid(
+                    String::from("A Post can only be restored as a Post"),
+                ),
+            );
+        }
+        let result = validate_restored_post(original_hash, original_record, restored_record, tag)?;
+        if result.ne(&ValidateCallbackResult::Valid) {
+            return Ok(result);
+        }
+    } else if crate::has_entry_type(original_record.action(), UnitEntryTypes::Comment)? {
+        if !tag.0.is_empty() {
+            return Ok(
+                ValidateCallbackResult::Invalid(
+                    String::from("The restoration links of a Comment must have an empty tag"),
+                ),
+            );
+        }
+        let original_comment = crate::Comment::try_from(original_record)?;
+        let restored_comment = crate::Comment::try_from(restored_record)?;
+        if original_comment.ne(&restored_comment) {
+            return Ok(
+                ValidateCallbackResult::Invalid(
+                    String::from("A restored Comment must be identical to the deleted one"),
+                ),
+            );
+        }
+    } else {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only Posts and Comments can be restored"),
+            ),
+        );
+    }
+    if restored_create.author.ne(author) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only the author of the restored copy can link it to the original"),
+            ),
+        );
+    }
+    crate::validate_author_or_moderator(
+        config,
+        author,
+        &original_create.author,
+        "Only the original author or a moderator can restore deleted content",
+    )
+}
+pub fn validate_create_link_restored_from(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+    config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    validate_restoration(&action.author, target_address, base_address, tag, config)
+}
+pub fn validate_delete_link_restored_from(
+    _action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+    _config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(
+        ValidateCallbackResult::Invalid(
+            String::from("RestoredFrom links cannot be deleted"),
+        ),
+    )
+}
+pub fn validate_create_link_restored_as(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+    config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    validate_restoration(&action.author, base_address, target_address, tag, config)
+}
+pub fn validate_delete_link_restored_as(
+    _action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+    _config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(
+        ValidateCallbackResult::Invalid(
+            String::from("RestoredAs links cannot be deleted"),
+        ),
+    )
+}
+/// Validates a PostToComments or CommentToReplies link whose base is not the Post or Comment
+/// its target refers to. Such a link is only valid when its tag holds the [`RestorationProof`]
+/// that the base is a restored copy of `original_hash`, and when it is created by whoever made
+/// the latest restoration, by the author of the linked Comment or by a moderator.
+pub fn validate_link_to_restored_copy(
+    action: &CreateLink,
+    base_hash: ActionHash,
+    original_hash: &ActionHash,
+    comment_author: &AgentPubKey,
+    tag: LinkTag,
+    config: &ForumConfig,
+    wrong_base_error: &str,
+) -> ExternResult<ValidateCallbackResult> {
+    if tag.0.is_empty() {
+        return Ok(ValidateCallbackResult::Invalid(String::from(wrong_base_error)));
+    }
+    let Ok(proof) = RestorationProof::try_from(
+        SerializedBytes::from(UnsafeBytes::from(tag.into_inner())),
+    ) else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(wrong_base_error)));
+    };
+    let mut copy_hash = base_hash;
+    let mut restorer: Option<AgentPubKey> = None;
+    for restored_from_hash in proof.0 {
+        let record = must_get_valid_record(restored_from_hash)?;
+        let Action::CreateLink(create_link) = record.action() else {
+            return Ok(ValidateCallbackResult::Invalid(String::from(wrong_base_error)));
+        };
+        let link_type = LinkTypes::from_type(create_link.zome_index, create_link.link_type)?;
+        if !matches!(link_type, Some(LinkTypes::RestoredFrom))
+            || create_link.base_address.ne(&AnyLinkableHash::from(copy_hash.clone()))
+        {
+            return Ok(ValidateCallbackResult::Invalid(String::from(wrong_base_error)));
+        }
+        let Some(restored_from) = create_link.target_address.clone().into_action_hash() else {
+            return Ok(ValidateCallbackResult::Invalid(String::from(wrong_base_error)));
+        };
+        restorer.get_or_insert(create_link.author.clone());
+        copy_hash = restored_from;
+    }
+    let Some(restorer) = restorer.filter(|_| copy_hash.eq(original_hash)) else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(wrong_base_error)));
+    };
+    if action.author.eq(comment_author) {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    crate::validate_author_or_moderator(
+        config,
+        &action.author,
+        &restorer,
+        "Only whoever restored a Post or Comment, the author of a Comment or a moderator can link it to the restored copy",
+    )
+}
@@ -0,0 +1,181 @@
+use hdi::prelude::*;
+use crate::ForumConfig;
+/// The path under which every tag is indexed, as `tags.<tag>`.
+pub const TAGS_PATH: &str = "tags";
+pub fn tag_path(tag: &str) -> Path {
+    Path::from(format!("{TAGS_PATH}.{tag}"))
+}
+/// Tags are lowercase words made of ASCII letters and digits, joined by single dashes.
+pub fn validate_tag(tag: &str, config: &ForumConfig) -> ValidateCallbackResult {
+    if tag.is_empty() {
+        return ValidateCallbackResult::Invalid(String::from("Tags cannot be empty"));
+    }
+    if tag.len() > config.max_tag_length {
+        return ValidateCallbackResult::Invalid(
+            format!("Tags cannot be longer than {} characters", config.max_tag_length),
+        );
+    }
+    let well_formed = tag
+        .split('-')
+        .all(|word| {
+            !word.is_empty()
+                && word.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        });
+    if !well_formed {
+        return ValidateCallbackResult::Invalid(
+            format!(
+                "Tag {tag:?} must only contain lowercase letters and digits, separated by single dashes"
+            ),
+        );
+    }
+    ValidateCallbackResult::Valid
+}
+pub fn validate_tags(tags: &[String], config: &ForumConfig) -> ValidateCallbackResult {
+    if tags.len() > config.max_tags_per_post {
+        return ValidateCallbackResult::Invalid(
+            format!("A Post cannot have more than {} tags", config.max_tags_per_post),
+        );
+    }
+    for (i, tag) in tags.iter().enumerate() {
+        if tags[..i].contains(tag) {
+            return ValidateCallbackResult::Invalid(format!("Tag {tag:?} is repeated"));
+        }
+        let result = validate_tag(tag, config);
+        if result != ValidateCallbackResult::Valid {
+            return result;
+        }
+    }
+    ValidateCallbackResult::Valid
+}
+/// TagPath links only build the `tags.<tag>` tree: either the root links to the `tags` path,
+/// or the `tags` path links to a well-formed tag. The link tag holds the child's component.
+pub fn validate_create_link_tag_path(
+    _action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+    config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    let component = Component::try_from(SerializedBytes::from(UnsafeBytes::from(tag.into_inner())))
+        .and_then(|component| String::try_from(&component));
+    let Ok(component) = component else {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("TagPath links must have a path component as their tag"),
+            ),
+        );
+    };
+    let child_path = if base_address.eq(&hdi::hash_path::path::root_hash()?) {
+        if component.ne(TAGS_PATH) {
+            return Ok(
+                ValidateCallbackResult::Invalid(
+                    format!("Only the {TAGS_PATH:?} path can be linked from the root"),
+                ),
+            );
+        }
+        Path::from(TAGS_PATH)
+    } else {
+        let tags_hash = Path::from(TAGS_PATH).path_entry_hash()?;
+        if base_address.ne(&AnyLinkableHash::from(tags_hash)) {
+            return Ok(
+                ValidateCallbackResult::Invalid(
+                    String::from("TagPath links must have the tags path as their base"),
+                ),
+            );
+        }
+        let result = validate_tag(&component, config);
+        if result != ValidateCallbackResult::Valid {
+            return Ok(result);
+        }
+        tag_path(&component)
+    };
+    if target_address.ne(&AnyLinkableHash::from(child_path.path_entry_hash()?)) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("TagPath links must point to the path named by their tag"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_delete_link_tag_path(
+    _action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+    _config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from("TagPath links cannot be deleted")))
+}
+pub fn validate_create_link_tag_to_posts(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+    config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    let Ok(post_tag) = String::from_utf8(tag.into_inner()) else {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("TagToPosts links must have the post tag as their tag"),
+            ),
+        );
+    };
+    let result = validate_tag(&post_tag, config);
+    if result != ValidateCallbackResult::Valid {
+        return Ok(result);
+    }
+    if base_address.ne(&AnyLinkableHash::from(tag_path(&post_tag).path_entry_hash()?)) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("TagToPosts links must have the path of their tag as their base"),
+            ),
+        );
+    }
+    let action_hash = target_address
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("No action hash associated with link"))
+            ),
+        )?;
+    let record = must_get_valid_record(action_hash)?;
+    let _post: crate::Post = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("Linked action must reference an entry"))
+            ),
+        )?;
+    let Action::Create(create) = record.action() else {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("TagToPosts links must point to the original Create of a Post"),
+            ),
+        );
+    };
+    if action.author.ne(&create.author) {
+        return Ok(
+            ValidateCallbackResult::Invalid(String::from("Only the author of a Post can tag it")),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_delete_link_tag_to_posts(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+    config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    crate::validate_author_or_moderator(
+        config,
+        &action.author,
+        &original_action.author,
+        "Only the author of a TagToPosts link or a moderator can delete it",
+    )
+}
@@ -0,0 +1,5 @@
+/// Whether the text contains control characters, optionally allowing line breaks and tabs.
+pub fn contains_control_characters(text: &str, allow_line_breaks: bool) -> bool {
+    text.chars()
+        .any(|c| c.is_control() && !(allow_line_breaks && matches!(c, '\n' | '\r' | '\t')))
+}
@@ -0,0 +1,156 @@
+use hdi::prelude::*;
+use crate::{ForumConfig, UnitEntryTypes};
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VoteDirection {
+    Up,
+    Down,
+}
+impl VoteDirection {
+    /// The tag of the TargetToVotes link pointing to a vote in this direction.
+    pub fn link_tag(&self) -> LinkTag {
+        match self {
+            VoteDirection::Up => LinkTag::new("up"),
+            VoteDirection::Down => LinkTag::new("down"),
+        }
+    }
+    pub fn from_link_tag(tag: &LinkTag) -> Option<VoteDirection> {
+        match tag.0.as_slice() {
+            b"up" => Some(VoteDirection::Up),
+            b"down" => Some(VoteDirection::Down),
+            _ => None,
+        }
+    }
+}
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct Vote {
+    pub target_hash: ActionHash,
+    pub direction: VoteDirection,
+}
+pub fn validate_create_vote(
+    action: EntryCreationAction,
+    vote: Vote,
+    _config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    let record = must_get_valid_record(vote.target_hash.clone())?;
+    if !crate::has_entry_type(record.action(), UnitEntryTypes::Post)?
+        && !crate::has_entry_type(record.action(), UnitEntryTypes::Comment)?
+    {
+        return Ok(
+            ValidateCallbackResult::Invalid(String::from("Votes must target a Post or a Comment")),
+        );
+    }
+    let target_timestamp = record.action().timestamp();
+    if action.timestamp() < &target_timestamp {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("A Vote cannot be dated before its target"),
+            ),
+        );
+    }
+    if let EntryCreationAction::Create(create) = action {
+        let active_votes = crate::must_get_active_creates_of_type(
+            &create.author,
+            create.prev_action,
+            target_timestamp,
+            UnitEntryTypes::Vote,
+        )?;
+        for vote_hash in active_votes {
+            let existing_vote = Vote::try_from(must_get_valid_record(vote_hash)?)?;
+            if existing_vote.target_hash.eq(&vote.target_hash) {
+                return Ok(
+                    ValidateCallbackResult::Invalid(
+                        String::from("An agent can only have one active vote on each target"),
+                    ),
+                );
+            }
+        }
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_update_vote(
+    _action: Update,
+    _vote: Vote,
+    _original_action: EntryCreationAction,
+    _original_vote: Vote,
+    _config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from("Votes cannot be updated")))
+}
+pub fn validate_delete_vote(
+    action: Delete,
+    original_action: EntryCreationAction,
+    _original_vote: Vote,
+    _config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author.ne(original_action.author()) {
+        return Ok(
+            ValidateCallbackResult::Invalid(String::from("Only the author of a Vote can delete it")),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_create_link_target_to_votes(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+    _config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    let action_hash = target_address
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("No action hash associated with link"))
+            ),
+        )?;
+    let record = must_get_valid_record(action_hash)?;
+    let vote: crate::Vote = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("Linked action must reference an entry"))
+            ),
+        )?;
+    if base_address.ne(&AnyLinkableHash::from(vote.target_hash.clone())) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("TargetToVotes links must have the vote's target as their base"),
+            ),
+        );
+    }
+    if tag.ne(&vote.direction.link_tag()) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("TargetToVotes links must have the vote direction as their tag"),
+            ),
+        );
+    }
+    if action.author.ne(record.action().author()) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only the author of a Vote can link it to its target"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_delete_link_target_to_votes(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+    _config: &ForumConfig,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author.ne(&original_action.author) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only the author of a TargetToVotes link can delete it"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
@@ -0,0 +1,263 @@
+pub mod profile;
+use hdi::prelude::*;
+pub use profile::*;
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "type")]
+#[hdk_entry_types]
+#[unit_enum(UnitEntryTypes)]
+pub enum EntryTypes {
+    Profile(Profile),
+}
+#[derive(Serialize, Deserialize)]
+#[hdk_link_types]
+pub enum LinkTypes {
+    AgentToProfile,
+    NicknamePath,
+    NicknameToProfile,
+}
+#[hdk_extern]
+pub fn genesis_self_check(_data: GenesisSelfCheckData) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Valid)
+}
+#[hdk_extern]
+pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
+    match op.flattened::<EntryTypes, LinkTypes>()? {
+        FlatOp::StoreEntry(store_entry) => match store_entry {
+            OpEntry::CreateEntry { app_entry, action } => {
+                validate_create_entry(EntryCreationAction::Create(action), app_entry)
+            }
+            OpEntry::UpdateEntry { app_entry, action, .. } => {
+                validate_create_entry(EntryCreationAction::Update(action), app_entry)
+            }
+            _ => Ok(ValidateCallbackResult::Valid),
+        },
+        FlatOp::RegisterUpdate(update_entry) => match update_entry {
+            OpUpdate::Entry { app_entry, action } => {
+                let original_record = must_get_valid_record(
+                    action.original_action_address.clone(),
+                )?;
+                validate_update_entry(action, app_entry, original_record)
+            }
+            _ => Ok(ValidateCallbackResult::Valid),
+        },
+        FlatOp::RegisterDelete(delete_entry) => validate_delete(delete_entry.action),
+        FlatOp::RegisterCreateLink {
+            link_type,
+            base_address,
+            target_address,
+            tag,
+            action,
+        } => validate_create_link(link_type, action, base_address, target_address, tag),
+        FlatOp::RegisterDeleteLink {
+            link_type,
+            base_address,
+            target_address,
+            tag,
+            original_action,
+            action,
+        } => {
+            validate_delete_link(
+                link_type,
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            )
+        }
+        FlatOp::StoreRecord(store_record) => match store_record {
+            OpRecord::CreateEntry { app_entry, action } => {
+                validate_create_entry(EntryCreationAction::Create(action), app_entry)
+            }
+            OpRecord::UpdateEntry { original_action_hash, app_entry, action, .. } => {
+                let result = validate_create_entry(
+                    EntryCreationAction::Update(action.clone()),
+                    app_entry.clone(),
+                )?;
+                let ValidateCallbackResult::Valid = result else {
+                    return Ok(result);
+                };
+                let original_record = must_get_valid_record(original_action_hash)?;
+                validate_update_entry(action, app_entry, original_record)
+            }
+            OpRecord::DeleteEntry { action, .. } => validate_delete(action),
+            OpRecord::CreateLink {
+                base_address,
+                target_address,
+                tag,
+                link_type,
+                action,
+            } => validate_create_link(link_type, action, base_address, target_address, tag),
+            OpRecord::DeleteLink { original_action_hash, base_address, action } => {
+                let record = must_get_valid_record(original_action_hash)?;
+                let create_link = match record.action() {
+                    Action::CreateLink(create_link) => create_link.clone(),
+                    _ => {
+                        return Ok(
+                            ValidateCallbackResult::Invalid(
+                                "The action that a DeleteLink deletes must be a CreateLink"
+                                    .to_string(),
+                            ),
+                        );
+                    }
+                };
+                let Some(link_type) = LinkTypes::from_type(
+                    create_link.zome_index,
+                    create_link.link_type,
+                )? else {
+                    return Ok(ValidateCallbackResult::Valid);
+                };
+                validate_delete_link(
+                    link_type,
+                    action,
+                    create_link.clone(),
+                    base_address,
+                    create_link.target_address,
+                    create_link.tag,
+                )
+            }
+            _ => Ok(ValidateCallbackResult::Valid),
+        },
+        FlatOp::RegisterAgentActivity(_) => Ok(ValidateCallbackResult::Valid),
+    }
+}
+fn validate_create_entry(
+    action: EntryCreationAction,
+    app_entry: EntryTypes,
+) -> ExternResult<ValidateCallbackResult> {
+    match app_entry {
+        EntryTypes::Profile(profile) => validate_create_profile(action, profile),
+    }
+}
+fn validate_update_entry(
+    action: Update,
+    app_entry: EntryTypes,
+    original_record: Record,
+) -> ExternResult<ValidateCallbackResult> {
+    let original_action = match EntryCreationAction::try_from(
+        original_record.action().clone(),
+    ) {
+        Ok(action) => action,
+        Err(e) => {
+            return Ok(
+                ValidateCallbackResult::Invalid(
+                    format!("Expected to get EntryCreationAction from Action: {e:?}"),
+                ),
+            );
+        }
+    };
+    match app_entry {
+        EntryTypes::Profile(profile) => {
+            let original_profile = match Profile::try_from(original_record) {
+                Ok(entry) => entry,
+                Err(e) => {
+                    return Ok(
+                        ValidateCallbackResult::Invalid(
+                            format!("Expected to get Profile from Record: {e:?}"),
+                        ),
+                    );
+                }
+            };
+            validate_update_profile(action, profile, original_action, original_profile)
+        }
+    }
+}
+fn validate_delete(action: Delete) -> ExternResult<ValidateCallbackResult> {
+    let original_record = must_get_valid_record(action.deletes_address.clone())?;
+    let original_action = match EntryCreationAction::try_from(
+        original_record.action().clone(),
+    ) {
+        Ok(action) => action,
+        Err(e) => {
+            return Ok(
+                ValidateCallbackResult::Invalid(
+                    format!("Expected to get EntryCreationAction from Action: {e:?}"),
+                ),
+            );
+        }
+    };
+    let EntryType::App(app_entry_type) = original_action.entry_type() else {
+        return Ok(ValidateCallbackResult::Valid);
+    };
+    let Some(entry) = original_record.entry().as_option() else {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Original record for a delete must contain an entry".to_string(),
+            ),
+        );
+    };
+    let Some(original_app_entry) = EntryTypes::deserialize_from_type(
+        app_entry_type.zome_index,
+        app_entry_type.entry_index,
+        entry,
+    )? else {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                "Original app entry must be one of the defined entry types for this zome"
+                    .to_string(),
+            ),
+        );
+    };
+    match original_app_entry {
+        EntryTypes::Profile(original_profile) => {
+            validate_delete_profile(action, original_action, original_profile)
+        }
+    }
+}
+fn validate_create_link(
+    link_type: LinkTypes,
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    match link_type {
+        LinkTypes::AgentToProfile => {
+            validate_create_link_agent_to_profile(action, base_address, target_address, tag)
+        }
+        LinkTypes::NicknamePath => {
+            validate_create_link_nickname_path(action, base_address, target_address, tag)
+        }
+        LinkTypes::NicknameToProfile => {
+            validate_create_link_nickname_to_profile(action, base_address, target_address, tag)
+        }
+    }
+}
+fn validate_delete_link(
+    link_type: LinkTypes,
+    action: DeleteLink,
+    original_action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    match link_type {
+        LinkTypes::AgentToProfile => {
+            validate_delete_link_agent_to_profile(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            )
+        }
+        LinkTypes::NicknamePath => {
+            validate_delete_link_nickname_path(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            )
+        }
+        LinkTypes::NicknameToProfile => {
+            validate_delete_link_nickname_to_profile(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            )
+        }
+    }
+}
@@ -0,0 +1,322 @@
+use hdi::prelude::*;
+use crate::UnitEntryTypes;
+pub const MIN_NICKNAME_LENGTH: usize = 3;
+pub const MAX_NICKNAME_LENGTH: usize = 32;
+pub const MAX_BIO_LENGTH: usize = 1_000;
+/// The path under which nicknames are indexed for search, as `all_nicknames.<prefix>`.
+pub const ALL_NICKNAMES_PATH: &str = "all_nicknames";
+/// How many actions back the nickname index links look in their author's chain for the Profile
+/// they index: the coordinator writes them right after creating or updating the Profile.
+const NICKNAME_INDEX_LOOKBACK: u32 = 8;
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct Profile {
+    /// Unique on a best-effort basis only: the coordinator refuses nicknames that it can see
+    /// taken, but two agents claiming the same nickname at once can both get it.
+    pub nickname: String,
+    /// A URL or hash pointing at the agent's avatar image.
+    pub avatar: Option<String>,
+    pub bio: String,
+}
+/// The search prefix of a nickname: its first characters, lowercased.
+pub fn nickname_prefix(nickname: &str) -> String {
+    nickname.chars().take(MIN_NICKNAME_LENGTH).collect::<String>().to_lowercase()
+}
+pub fn nickname_prefix_path(nickname: &str) -> Path {
+    Path::from(format!("{ALL_NICKNAMES_PATH}.{}", nickname_prefix(nickname)))
+}
+/// Nicknames are made of ASCII letters, digits and underscores.
+pub fn validate_nickname(nickname: &str) -> ValidateCallbackResult {
+    if nickname.len() < MIN_NICKNAME_LENGTH {
+        return ValidateCallbackResult::Invalid(
+            format!("Nicknames must be at least {MIN_NICKNAME_LENGTH} characters long"),
+        );
+    }
+    if nickname.len() > MAX_NICKNAME_LENGTH {
+        return ValidateCallbackResult::Invalid(
+            format!("Nicknames cannot be longer than {MAX_NICKNAME_LENGTH} characters"),
+        );
+    }
+    if !nickname.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return ValidateCallbackResult::Invalid(
+            format!("Nickname {nickname:?} must only contain letters, digits and underscores"),
+        );
+    }
+    ValidateCallbackResult::Valid
+}
+pub fn validate_create_profile(
+    _action: EntryCreationAction,
+    profile: Profile,
+) -> ExternResult<ValidateCallbackResult> {
+    let nickname_result = validate_nickname(&profile.nickname);
+    if nickname_result != ValidateCallbackResult::Valid {
+        return Ok(nickname_result);
+    }
+    if profile.bio.chars().count() > MAX_BIO_LENGTH {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                format!("Bio cannot be longer than {MAX_BIO_LENGTH} characters"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_update_profile(
+    action: Update,
+    _profile: Profile,
+    original_action: EntryCreationAction,
+    _original_profile: Profile,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author.ne(original_action.author()) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only the owner of a Profile can update it"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_delete_profile(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_profile: Profile,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from("Profiles cannot be deleted")))
+}
+fn must_get_profile_create(profile_address: AnyLinkableHash) -> ExternResult<Option<Create>> {
+    let action_hash = profile_address
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("No action hash associated with link"))
+            ),
+        )?;
+    let record = must_get_valid_record(action_hash)?;
+    let _profile: crate::Profile = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("Linked action must reference an entry"))
+            ),
+        )?;
+    match record.action() {
+        Action::Create(create) => Ok(Some(create.clone())),
+        _ => Ok(None),
+    }
+}
+pub fn validate_create_link_agent_to_profile(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if base_address.ne(&AnyLinkableHash::from(action.author.clone())) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("AgentToProfile links must have their author as their base"),
+            ),
+        );
+    }
+    let Some(create) = must_get_profile_create(target_address)? else {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("AgentToProfile links must point to the original Create of a Profile"),
+            ),
+        );
+    };
+    if action.author.ne(&create.author) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Agents can only link to their own Profile"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_delete_link_agent_to_profile(
+    _action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from("AgentToProfile links cannot be deleted")))
+}
+/// The latest Profile that the author of the given link created or updated shortly before it,
+/// along with the hash of that Profile's original Create.
+fn must_get_indexed_profile(action: &CreateLink) -> ExternResult<Option<(ActionHash, Profile)>> {
+    let activity = must_get_agent_activity(
+        action.author.clone(),
+        ChainFilter::new(action.prev_action.clone()).take(NICKNAME_INDEX_LOOKBACK),
+    )?;
+    let profile_entry_def = ScopedEntryDefIndex::try_from(UnitEntryTypes::Profile)?;
+    let latest_profile_action = activity
+        .into_iter()
+        .filter(|item| {
+            matches!(
+                item.action.action().entry_type(),
+                Some(EntryType::App(app_entry_def))
+                    if app_entry_def.zome_index == profile_entry_def.zome_index
+                        && app_entry_def.entry_index == profile_entry_def.zome_type
+            )
+        })
+        .max_by_key(|item| item.action.action().action_seq());
+    let Some(item) = latest_profile_action else {
+        return Ok(None);
+    };
+    let original_profile_hash = match item.action.action() {
+        Action::Update(update) => update.original_action_address.clone(),
+        _ => item.action.as_hash().clone(),
+    };
+    let Some(entry_hash) = item.action.action().entry_hash() else {
+        return Ok(None);
+    };
+    let profile = Profile::try_from(must_get_entry(entry_hash.clone())?.content)?;
+    Ok(Some((original_profile_hash, profile)))
+}
+/// NicknamePath links only build the `all_nicknames.<prefix>` tree: either the root links to
+/// the `all_nicknames` path, or that path links to the prefix of the nickname of the Profile
+/// that the link author just created or updated. The link tag holds the child's component.
+pub fn validate_create_link_nickname_path(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let component = Component::try_from(SerializedBytes::from(UnsafeBytes::from(tag.into_inner())))
+        .and_then(|component| String::try_from(&component));
+    let Ok(component) = component else {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("NicknamePath links must have a path component as their tag"),
+            ),
+        );
+    };
+    let child_path = if base_address.eq(&hdi::hash_path::path::root_hash()?) {
+        if component.ne(ALL_NICKNAMES_PATH) {
+            return Ok(
+                ValidateCallbackResult::Invalid(
+                    format!("Only the {ALL_NICKNAMES_PATH:?} path can be linked from the root"),
+                ),
+            );
+        }
+        Path::from(ALL_NICKNAMES_PATH)
+    } else {
+        let all_nicknames_hash = Path::from(ALL_NICKNAMES_PATH).path_entry_hash()?;
+        if base_address.ne(&AnyLinkableHash::from(all_nicknames_hash)) {
+            return Ok(
+                ValidateCallbackResult::Invalid(
+                    String::from("NicknamePath links must have the all_nicknames path as their base"),
+                ),
+            );
+        }
+        let Some((_, profile)) = must_get_indexed_profile(&action)? else {
+            return Ok(
+                ValidateCallbackResult::Invalid(
+                    String::from("NicknamePath links must follow the Profile whose nickname they index"),
+                ),
+            );
+        };
+        if component.ne(&nickname_prefix(&profile.nickname)) {
+            return Ok(
+                ValidateCallbackResult::Invalid(
+                    String::from("NicknamePath links must hold the prefix of their author's nickname"),
+                ),
+            );
+        }
+        nickname_prefix_path(&profile.nickname)
+    };
+    if target_address.ne(&AnyLinkableHash::from(child_path.path_entry_hash()?)) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("NicknamePath links must point to the path named by their tag"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_delete_link_nickname_path(
+    _action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from("NicknamePath links cannot be deleted")))
+}
+pub fn validate_create_link_nickname_to_profile(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let Ok(nickname) = String::from_utf8(tag.into_inner()) else {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("NicknameToProfile links must have the nickname as their tag"),
+            ),
+        );
+    };
+    let nickname_result = validate_nickname(&nickname);
+    if nickname_result != ValidateCallbackResult::Valid {
+        return Ok(nickname_result);
+    }
+    let prefix_hash = nickname_prefix_path(&nickname).path_entry_hash()?;
+    if base_address.ne(&AnyLinkableHash::from(prefix_hash)) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("NicknameToProfile links must have the nickname's prefix path as their base"),
+            ),
+        );
+    }
+    let Some(create) = must_get_profile_create(target_address.clone())? else {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("NicknameToProfile links must point to the original Create of a Profile"),
+            ),
+        );
+    };
+    if action.author.ne(&create.author) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Agents can only index the nickname of their own Profile"),
+            ),
+        );
+    }
+    let indexed_profile = must_get_indexed_profile(&action)?;
+    let Some((original_profile_hash, profile)) = indexed_profile else {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("NicknameToProfile links must follow the Profile whose nickname they index"),
+            ),
+        );
+    };
+    if AnyLinkableHash::from(original_profile_hash).ne(&target_address)
+        || profile.nickname.ne(&nickname)
+    {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("NicknameToProfile links must hold the current nickname of the linked Profile"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_delete_link_nickname_to_profile(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author.ne(&original_action.author) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only the owner of a Profile can remove its nickname from the index"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
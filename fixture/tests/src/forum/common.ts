@@ -0,0 +1,80 @@
+import { Scenario, Player, Conductor, enableAndGetAgentApp } from '@holochain/tryorama';
+import { AgentPubKey, encodeHashToBase64 } from '@holochain/client';
+
+// Construct proper paths for your app.
+// This assumes app bundle created by the `hc app pack` command.
+export const testAppPath = process.cwd() + '/../workdir/forum.happ';
+
+export interface PendingPlayer {
+  conductor: Conductor;
+  agentPubKey: AgentPubKey;
+}
+
+// Adds conductors and generates their agent keys before installing the forum, so that the
+// DNA properties can name some of the agents, e.g. as moderators or as the progenitor.
+export async function addPendingPlayers(scenario: Scenario, count: number): Promise<PendingPlayer[]> {
+  const pendingPlayers: PendingPlayer[] = [];
+  for (let i = 0; i < count; i++) {
+    const conductor = await scenario.addConductor();
+    const agentPubKey = await conductor.adminWs().generateAgentPubKey();
+    pendingPlayers.push({ conductor, agentPubKey });
+  }
+  return pendingPlayers;
+}
+
+// Installs the forum for a pending player with the given DNA properties. Every player of a
+// scenario must get the same properties to join the same network.
+export async function installForum(
+  scenario: Scenario,
+  pendingPlayer: PendingPlayer,
+  properties: object,
+  membraneProof: Uint8Array | undefined = undefined,
+): Promise<Player> {
+  const { conductor, agentPubKey } = pendingPlayer;
+  const appInfo = await conductor.installApp({
+    appBundleSource: { path: testAppPath },
+    options: {
+      agentPubKey,
+      networkSeed: scenario.networkSeed,
+      rolesSettings: {
+        forum: {
+          type: "provisioned",
+          value: {
+            membrane_proof: membraneProof,
+            modifiers: { properties },
+          },
+        },
+      },
+    },
+  } as any);
+  const adminWs = conductor.adminWs();
+  const port = await conductor.attachAppInterface();
+  const issued = await adminWs.issueAppAuthenticationToken({
+    installed_app_id: appInfo.installed_app_id,
+  });
+  const appWs = await conductor.connectAppWs(issued.token, port);
+  const agentApp = await enableAndGetAgentApp(adminWs, appWs, appInfo);
+  return { conductor, appWs, ...agentApp };
+}
+
+// Adds `count` players sharing the DNA properties returned by `properties`, which gets the
+// players' agent keys.
+export async function addPlayersWithProperties(
+  scenario: Scenario,
+  count: number,
+  properties: (agents: AgentPubKey[]) => object,
+): Promise<Player[]> {
+  const pendingPlayers = await addPendingPlayers(scenario, count);
+  const sharedProperties = properties(pendingPlayers.map(p => p.agentPubKey));
+  const players: Player[] = [];
+  for (const pendingPlayer of pendingPlayers) {
+    players.push(await installForum(scenario, pendingPlayer, sharedProperties));
+  }
+  await scenario.shareAllAgents();
+  return players;
+}
+
+// Agent keys are written in the properties the same way as in `happ.yaml`.
+export function agentKeyProperty(agent: AgentPubKey): string {
+  return encodeHashToBase64(agent);
+}
@@ -1,10 +1,10 @@
-import { assert, test } from "vitest";
+import { assert, expect, test } from "vitest";
 
 import { runScenario, dhtSync, CallableCell } from '@holochain/tryorama';
-import { NewEntryAction, ActionHash, Record, AppBundleSource,  fakeActionHash, fakeAgentPubKey, fakeEntryHash } from '@holochain/client';
+import { NewEntryAction, ActionHash, Record, Link, AppBundleSource,  fakeActionHash, fakeAgentPubKey, fakeEntryHash } from '@holochain/client';
 import { decode } from '@msgpack/msgpack';
 
-import { createPost } from './common.js';
+import { createPost, createRawLink, getPathHash } from './common.js';
 
 test('create a Post and get all posts', async () => {
   await runScenario(async scenario => {
@@ -65,3 +65,54 @@ test('create a Post and get all posts', async () => {
   });
 });
 
+
+test('AllPosts links are validated', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/forum.happ';
+
+    // Set up the app to be installed 
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates two Posts
+    const postHash = (await createPost(alice.cells[0])).signed_action.hashed.hash;
+    const otherPostHash = (await createPost(alice.cells[0])).signed_action.hashed.hash;
+    const allPostsHash = await getPathHash(alice.cells[0], "all_posts");
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // The link must have the all_posts path as its base
+    await expect(createRawLink(alice.cells[0], otherPostHash, postHash, "AllPosts"))
+      .rejects.toThrow(/AllPosts links must have the all_posts path as their base/);
+
+    // Its tag must be empty
+    await expect(createRawLink(alice.cells[0], allPostsHash, postHash, "AllPosts", new Uint8Array([1])))
+      .rejects.toThrow(/AllPosts links must have an empty tag/);
+
+    // Only the author of a Post can list it
+    await expect(createRawLink(bob.cells[0], allPostsHash, postHash, "AllPosts"))
+      .rejects.toThrow(/Only the author of a Post can add it to AllPosts/);
+
+    // And only the link author can delete the link
+    const links: Link[] = await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_all_posts",
+      payload: null,
+    });
+    assert.equal(links.length, 2);
+    await expect(bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "delete_raw_link",
+      payload: links[0].create_link_hash,
+    })).rejects.toThrow(/Only the author of an AllPosts link can delete it/);
+  });
+});
@@ -0,0 +1,107 @@
+import { assert, expect, test } from "vitest";
+
+import { runScenario, dhtSync, CallableCell } from '@holochain/tryorama';
+import { NewEntryAction, ActionHash, EntryHash, Record, Link, AppBundleSource, fakeDnaHash, fakeActionHash, fakeAgentPubKey, fakeEntryHash } from '@holochain/client';
+import { decode } from '@msgpack/msgpack';
+
+import { createPost } from './common.js';
+
+const CHUNK_SIZE = 256 * 1024;
+
+test('upload a file in chunks, attach it to a Post and download it', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/forum.happ';
+
+    // Set up the app to be installed 
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice uploads a file spanning two chunks
+    const content = new Uint8Array(CHUNK_SIZE + 10).map((_, i) => i % 256);
+    const chunks: EntryHash[] = [];
+    for (let start = 0; start < content.length; start += CHUNK_SIZE) {
+      chunks.push(await alice.cells[0].callZome({
+        zome_name: "posts",
+        fn_name: "upload_file_chunk",
+        payload: { bytes: content.slice(start, start + CHUNK_SIZE) },
+      }));
+    }
+    const fileRecord: Record = await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "create_file_metadata",
+      payload: { name: "log.txt", mime_type: "text/plain", chunks },
+    });
+    const fileHash = fileRecord.signed_action.hashed.hash;
+
+    // Alice attaches it to their Post
+    const postHash = (await createPost(alice.cells[0])).signed_action.hashed.hash;
+    await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "attach_file_to_post",
+      payload: { post_hash: postHash, file_metadata_hash: fileHash },
+    });
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob can't attach files to Alice's Post
+    await expect(bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "attach_file_to_post",
+      payload: { post_hash: postHash, file_metadata_hash: fileHash },
+    })).rejects.toThrow(/Only the author of a Post can attach files to it/);
+
+    // Bob finds the attachment and downloads it
+    const links: Link[] = await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_attachments_for_post",
+      payload: postHash,
+    });
+    assert.equal(links.length, 1);
+    const file: any = await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "download_file",
+      payload: links[0].target,
+    });
+    assert.equal(file.metadata.name, "log.txt");
+    assert.equal(file.metadata.size, content.length);
+    assert.deepEqual(new Uint8Array(file.content), content);
+  });
+});
+
+test('only the last chunk of a file can be partial', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/forum.happ';
+
+    // Set up the app to be installed 
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add a player with the test app to the Scenario.
+    const [alice] = await scenario.addPlayersWithApps([appSource]);
+
+    // Alice splits a file into a short chunk followed by a full one
+    const chunks: EntryHash[] = [];
+    for (const length of [10, CHUNK_SIZE]) {
+      chunks.push(await alice.cells[0].callZome({
+        zome_name: "posts",
+        fn_name: "upload_file_chunk",
+        payload: { bytes: new Uint8Array(length).fill(1) },
+      }));
+    }
+    await expect(alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "create_file_metadata",
+      payload: { name: "log.txt", mime_type: "text/plain", chunks },
+    })).rejects.toThrow(/Every chunk of a file but the last must hold/);
+  });
+});
@@ -0,0 +1,64 @@
+import { assert, test } from "vitest";
+
+import { runScenario, dhtSync, CallableCell } from '@holochain/tryorama';
+import { NewEntryAction, ActionHash, Record, AppBundleSource, fakeDnaHash, fakeActionHash, fakeAgentPubKey, fakeEntryHash } from '@holochain/client';
+import { decode } from '@msgpack/msgpack';
+
+import { createPost } from './common.js';
+
+test('add, list and remove private Bookmarks', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/forum.happ';
+
+    // Set up the app to be installed 
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    const firstPostHash = (await createPost(alice.cells[0])).signed_action.hashed.hash;
+    const secondPostHash = (await createPost(alice.cells[0])).signed_action.hashed.hash;
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob bookmarks both Posts
+    await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "add_bookmark",
+      payload: { target_hash: firstPostHash, folder: "later", note: null },
+    });
+    const bookmarkRecord: Record = await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "add_bookmark",
+      payload: { target_hash: secondPostHash, folder: null, note: "Useful" },
+    });
+
+    // Bob lists them newest first, then removes the first one
+    let bookmarks: Record[] = await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_my_bookmarks",
+      payload: null,
+    });
+    assert.equal(bookmarks.length, 2);
+    assert.deepEqual(bookmarks[0].signed_action.hashed.hash, bookmarkRecord.signed_action.hashed.hash);
+    await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "remove_bookmark",
+      payload: firstPostHash,
+    });
+    bookmarks = await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_my_bookmarks",
+      payload: null,
+    });
+    assert.equal(bookmarks.length, 1);
+    assert.deepEqual(bookmarks[0].signed_action.hashed.hash, bookmarkRecord.signed_action.hashed.hash);
+  });
+});
@@ -0,0 +1,324 @@
+import { assert, expect, test } from "vitest";
+
+import { runScenario, dhtSync, CallableCell } from '@holochain/tryorama';
+import { NewEntryAction, ActionHash, Record, Link, AppBundleSource, fakeDnaHash, fakeActionHash, fakeAgentPubKey, fakeEntryHash } from '@holochain/client';
+import { decode } from '@msgpack/msgpack';
+
+import { createChannel, sampleChannel, createPost, samplePost } from './common.js';
+
+test('create a Channel and file a Post in it', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/forum.happ';
+
+    // Set up the app to be installed 
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a Channel
+    const channelRecord: Record = await createChannel(alice.cells[0]);
+    const channelHash = channelRecord.signed_action.hashed.hash;
+
+    // Bob files a Post in it
+    const postRecord: Record = await createPost(
+      bob.cells[0],
+      await samplePost(bob.cells[0], { channel_hash: channelHash })
+    );
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Alice gets all Channels
+    const channelLinks: Link[] = await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_all_channels",
+      payload: null,
+    });
+    assert.equal(channelLinks.length, 1);
+    assert.deepEqual(channelLinks[0].target, channelHash);
+
+    // Alice finds Bob's Post in the Channel
+    const postLinks: Link[] = await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_posts_in_channel",
+      payload: channelHash,
+    });
+    assert.equal(postLinks.length, 1);
+    assert.deepEqual(postLinks[0].target, postRecord.signed_action.hashed.hash);
+  });
+});
+
+test('only channel moderators can move a Post to their Channel', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/forum.happ';
+
+    // Set up the app to be installed 
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates two Channels, and Bob files a Post in the first one
+    const fromChannelHash = (await createChannel(alice.cells[0])).signed_action.hashed.hash;
+    const toChannelHash = (await createChannel(
+      alice.cells[0],
+      await sampleChannel(alice.cells[0], { name: "Off-topic" })
+    )).signed_action.hashed.hash;
+    const postHash = (await createPost(
+      bob.cells[0],
+      await samplePost(bob.cells[0], { channel_hash: fromChannelHash })
+    )).signed_action.hashed.hash;
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    const moveInput = {
+      original_post_hash: postHash,
+      to_channel_hash: toChannelHash,
+    };
+
+    // Bob does not moderate the destination Channel, so they cannot move their Post there
+    await expect(bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "move_post_to_channel",
+      payload: moveInput,
+    })).rejects.toThrow(/Only channel moderators can move posts into a channel/);
+
+    // Alice created both Channels, so they can
+    await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "move_post_to_channel",
+      payload: moveInput,
+    });
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    let links: Link[] = await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_posts_in_channel",
+      payload: fromChannelHash,
+    });
+    assert.equal(links.length, 0);
+    links = await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_posts_in_channel",
+      payload: toChannelHash,
+    });
+    assert.equal(links.length, 1);
+    assert.deepEqual(links[0].target, postHash);
+  });
+});
+
+test('moving a Post out of a Channel requires moderating it, and deleting a moved Post unlists it', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/forum.happ';
+
+    // Set up the app to be installed 
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice and Bob each create a Channel, and Alice files a Post in their own
+    const aliceChannelHash = (await createChannel(alice.cells[0])).signed_action.hashed.hash;
+    const bobChannelHash = (await createChannel(
+      bob.cells[0],
+      await sampleChannel(bob.cells[0], { name: "Off-topic" })
+    )).signed_action.hashed.hash;
+    const postHash = (await createPost(
+      alice.cells[0],
+      await samplePost(alice.cells[0], { channel_hash: aliceChannelHash })
+    )).signed_action.hashed.hash;
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob moderates their Channel but not Alice's, so they can't pull the Post out of it
+    await expect(bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "move_post_to_channel",
+      payload: { original_post_hash: postHash, to_channel_hash: bobChannelHash },
+    })).rejects.toThrow(/Only channel moderators can move posts out of a channel/);
+
+    // Alice moves the Post to a second Channel of theirs, then deletes it
+    const otherChannelHash = (await createChannel(
+      alice.cells[0],
+      await sampleChannel(alice.cells[0], { name: "Announcements" })
+    )).signed_action.hashed.hash;
+    await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "move_post_to_channel",
+      payload: { original_post_hash: postHash, to_channel_hash: otherChannelHash },
+    });
+    await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "delete_post",
+      payload: postHash,
+    });
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // The Post is no longer listed in any Channel
+    for (const channelHash of [aliceChannelHash, otherChannelHash]) {
+      const links: Link[] = await bob.cells[0].callZome({
+        zome_name: "posts",
+        fn_name: "get_posts_in_channel",
+        payload: channelHash,
+      });
+      assert.equal(links.length, 0);
+    }
+  });
+});
+
+test('updating a Post cannot move it to another Channel', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/forum.happ';
+
+    // Set up the app to be installed 
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates two Channels, and Bob files a Post in the first one
+    const fromChannelHash = (await createChannel(alice.cells[0])).signed_action.hashed.hash;
+    const toChannelHash = (await createChannel(
+      alice.cells[0],
+      await sampleChannel(alice.cells[0], { name: "Off-topic" })
+    )).signed_action.hashed.hash;
+    const post = await samplePost(bob.cells[0], { channel_hash: fromChannelHash });
+    const postHash = (await createPost(bob.cells[0], post)).signed_action.hashed.hash;
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob can't move their Post by updating its channel
+    await expect(bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "update_post",
+      payload: {
+        original_post_hash: postHash,
+        previous_post_hash: postHash,
+        updated_post: { ...post, channel_hash: toChannelHash },
+      },
+    })).rejects.toThrow(/The channel of a Post cannot be changed by updating it/);
+
+    // Bob can still update the Post within its Channel
+    await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "update_post",
+      payload: {
+        original_post_hash: postHash,
+        previous_post_hash: postHash,
+        updated_post: { ...post, content: "Edited content" },
+      },
+    });
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    const links: Link[] = await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_posts_in_channel",
+      payload: fromChannelHash,
+    });
+    assert.equal(links.length, 1);
+    assert.deepEqual(links[0].target, postHash);
+  });
+});
+
+test('restoring a moved Post lists the copy in the Channel it was moved to', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/forum.happ';
+
+    // Set up the app to be installed 
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates two Channels, Bob files a Post in the first one and Alice moves it
+    const fromChannelHash = (await createChannel(alice.cells[0])).signed_action.hashed.hash;
+    const toChannelHash = (await createChannel(
+      alice.cells[0],
+      await sampleChannel(alice.cells[0], { name: "Off-topic" })
+    )).signed_action.hashed.hash;
+    const postHash = (await createPost(
+      bob.cells[0],
+      await samplePost(bob.cells[0], { channel_hash: fromChannelHash })
+    )).signed_action.hashed.hash;
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "move_post_to_channel",
+      payload: { original_post_hash: postHash, to_channel_hash: toChannelHash },
+    });
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob deletes their Post and restores it, though they don't moderate either Channel
+    await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "delete_post",
+      payload: postHash,
+    });
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    const restoredRecord: Record = await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "restore_post",
+      payload: postHash,
+    });
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // The copy is listed where the Post was moved to, not in its original Channel
+    let links: Link[] = await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_posts_in_channel",
+      payload: fromChannelHash,
+    });
+    assert.equal(links.length, 0);
+    links = await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_posts_in_channel",
+      payload: toChannelHash,
+    });
+    assert.equal(links.length, 1);
+    assert.deepEqual(links[0].target, restoredRecord.signed_action.hashed.hash);
+  });
+});
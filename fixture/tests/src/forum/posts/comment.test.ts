@@ -1,10 +1,10 @@
-import { assert, test } from "vitest";
+import { assert, expect, test } from "vitest";
 
 import { runScenario, dhtSync, CallableCell } from '@holochain/tryorama';
-import { NewEntryAction, ActionHash, Record, AppBundleSource, fakeDnaHash, fakeActionHash, fakeAgentPubKey, fakeEntryHash } from '@holochain/client';
+import { NewEntryAction, ActionHash, Record, Link, AppBundleSource, fakeDnaHash, fakeActionHash, fakeAgentPubKey, fakeEntryHash } from '@holochain/client';
 import { decode } from '@msgpack/msgpack';
 
-import { createComment, sampleComment } from './common.js';
+import { createComment, sampleComment, createPost, createRawLink } from './common.js';
 
 test('create Comment', async () => {
   await runScenario(async scenario => {
@@ -126,7 +126,8 @@ test('create and delete Comment', async () => {
       fn_name: "get_oldest_delete_for_comment",
       payload: record.signed_action.hashed.hash,
     });
-    assert.ok(oldestDeleteForComment);
+    assert.ok(oldestDeleteForComment.delete);
+    assert.equal(oldestDeleteForComment.restored_as, null);
         
     // Bob gets the deletions for Comment
     const deletesForComment = await bob.cells[0].callZome({
@@ -154,3 +155,93 @@ test('create and delete Comment', async () => {
 
   });
 });
+
+test('reply to a Comment and get the comment tree', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/forum.happ';
+
+    // Set up the app to be installed 
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a Comment
+    const sample = await sampleComment(alice.cells[0]);
+    const record: Record = await createComment(alice.cells[0], sample);
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob replies to Alice's Comment
+    const reply: Record = await createComment(bob.cells[0], {
+      ...sample,
+      comment: "I agree",
+      parent_comment_hash: record.signed_action.hashed.hash,
+    });
+    assert.ok(reply);
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Alice gets the comment tree for the Post
+    const tree: any[] = await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_comment_tree",
+      payload: sample.post_hash,
+    });
+    assert.equal(tree.length, 1);
+    assert.deepEqual(tree[0].record.signed_action.hashed.hash, record.signed_action.hashed.hash);
+    assert.equal(tree[0].replies.length, 1);
+    assert.deepEqual(tree[0].replies[0].record.signed_action.hashed.hash, reply.signed_action.hashed.hash);
+  });
+});
+
+test('Comments can only be linked to their own Post by their author', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/forum.happ';
+
+    // Set up the app to be installed 
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a Post, and Bob comments on a Post of their own
+    const alicePostHash = (await createPost(alice.cells[0])).signed_action.hashed.hash;
+    const sample = await sampleComment(bob.cells[0]);
+    const commentHash = (await createComment(bob.cells[0], sample)).signed_action.hashed.hash;
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob can't cross-post the Comment into Alice's thread
+    await expect(
+      createRawLink(bob.cells[0], alicePostHash, commentHash, "PostToComments"),
+    ).rejects.toThrow(/PostToComments links must have the commented Post as their base/);
+
+    // Alice can't link Bob's Comment to its Post again
+    await expect(
+      createRawLink(alice.cells[0], sample.post_hash, commentHash, "PostToComments"),
+    ).rejects.toThrow(/Only the author of a Comment can link it to its Post/);
+
+    // Alice's thread has no Comments
+    const links: Link[] = await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_comments_for_post",
+      payload: alicePostHash,
+    });
+    assert.equal(links.length, 0);
+  });
+});
@@ -1,5 +1,5 @@
 import { CallableCell } from '@holochain/tryorama';
-import { NewEntryAction, ActionHash, Record, AppBundleSource, fakeActionHash, fakeAgentPubKey, fakeEntryHash, fakeDnaHash } from '@holochain/client';
+import { NewEntryAction, ActionHash, EntryHash, Record, AppBundleSource, fakeActionHash, fakeAgentPubKey, fakeEntryHash, fakeDnaHash } from '@holochain/client';
 
 
 
@@ -8,6 +8,8 @@ export async function samplePost(cell: CallableCell, partialPost = {}) {
         ...{
 	  title: "Lorem ipsum dolor sit amet, consectetur adipiscing elit.",
 	  content: "Lorem ipsum dolor sit amet, consectetur adipiscing elit.",
+	  tags: [],
+	  channel_hash: null,
         },
         ...partialPost
     };
@@ -28,6 +30,7 @@ export async function sampleComment(cell: CallableCell, partialComment = {}) {
         ...{
 	  comment: "Lorem ipsum dolor sit amet, consectetur adipiscing elit.",
           post_hash: (await createPost(cell)).signed_action.hashed.hash,
+          parent_comment_hash: null,
         },
         ...partialComment
     };
@@ -41,3 +44,47 @@ export async function createComment(cell: CallableCell, comment = undefined): Pr
     });
 }
 
+
+
+export async function sampleChannel(cell: CallableCell, partialChannel = {}) {
+    return {
+        ...{
+	  name: "General",
+	  description: "Lorem ipsum dolor sit amet, consectetur adipiscing elit.",
+	  moderators: [],
+        },
+        ...partialChannel
+    };
+}
+
+export async function createChannel(cell: CallableCell, channel = undefined): Promise<Record> {
+    return cell.callZome({
+      zome_name: "posts",
+      fn_name: "create_channel",
+      payload: channel || await sampleChannel(cell),
+    });
+}
+
+// Writes a link without the coordinator's checks, only available in happs built with
+// `npm run build:happ:test`.
+export async function createRawLink(
+  cell: CallableCell,
+  base: Uint8Array,
+  target: Uint8Array,
+  linkType: string,
+  tag: Uint8Array = new Uint8Array(),
+): Promise<ActionHash> {
+  return cell.callZome({
+    zome_name: "posts",
+    fn_name: "create_raw_link",
+    payload: { base, target, link_type: linkType, tag },
+  });
+}
+
+export async function getPathHash(cell: CallableCell, path: string): Promise<EntryHash> {
+  return cell.callZome({
+    zome_name: "posts",
+    fn_name: "get_path_hash",
+    payload: path,
+  });
+}
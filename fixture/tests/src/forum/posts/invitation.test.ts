@@ -0,0 +1,87 @@
+import { assert, expect, test } from "vitest";
+
+import { runScenario, dhtSync, CallableCell } from '@holochain/tryorama';
+import { NewEntryAction, ActionHash, Record, Link, AppBundleSource, fakeDnaHash, fakeActionHash, fakeAgentPubKey, fakeEntryHash } from '@holochain/client';
+import { decode, encode } from '@msgpack/msgpack';
+
+import { createPost } from './common.js';
+import { addPendingPlayers, installForum, agentKeyProperty } from '../common.js';
+
+test('invite-only forums admit the agents invited by a moderator', async () => {
+  await runScenario(async scenario => {
+    const [alicePending, bobPending] = await addPendingPlayers(scenario, 2);
+    const properties = {
+      membership: "invite_only",
+      progenitor: agentKeyProperty(alicePending.agentPubKey),
+    };
+
+    // The progenitor joins without an invitation
+    const alice = await installForum(scenario, alicePending, properties);
+
+    // Alice invites Bob, who joins with the signed invitation as membrane proof
+    const signedInvitation = await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "create_invitation",
+      payload: bobPending.agentPubKey,
+    });
+    const bob = await installForum(scenario, bobPending, properties, encode(signedInvitation));
+    await scenario.shareAllAgents();
+
+    // Bob takes part in the forum
+    const record: Record = await createPost(bob.cells[0]);
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    const links: Link[] = await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_all_posts",
+      payload: null,
+    });
+    assert.equal(links.length, 1);
+    assert.deepEqual(links[0].target, record.signed_action.hashed.hash);
+  });
+});
+
+test('invite-only forums reject agents without a valid invitation', async () => {
+  await runScenario(async scenario => {
+    const [alicePending, bobPending, carolPending, davePending] = await addPendingPlayers(scenario, 4);
+    const properties = {
+      membership: "invite_only",
+      progenitor: agentKeyProperty(alicePending.agentPubKey),
+    };
+    const alice = await installForum(scenario, alicePending, properties);
+
+    // Bob can't join without an invitation
+    await expect(
+      installForum(scenario, bobPending, properties),
+    ).rejects.toThrow(/An invitation is required to join this forum/);
+
+    // Dave can't join with the invitation that Alice issued for Carol
+    const carolInvitation: any = await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "create_invitation",
+      payload: carolPending.agentPubKey,
+    });
+    await expect(
+      installForum(scenario, davePending, properties, encode(carolInvitation)),
+    ).rejects.toThrow(/The invitation was issued for a different agent/);
+
+    // Carol can't join with an invitation for another forum
+    const otherForumInvitation = {
+      ...carolInvitation,
+      invitation: { ...carolInvitation.invitation, dna_hash: await fakeDnaHash() },
+    };
+    await expect(
+      installForum(scenario, carolPending, properties, encode(otherForumInvitation)),
+    ).rejects.toThrow(/The invitation was issued for a different forum/);
+
+    // Carol can't join with an invitation that names an inviter who isn't a moderator
+    const forgedInvitation = {
+      ...carolInvitation,
+      invitation: { ...carolInvitation.invitation, inviter: davePending.agentPubKey },
+    };
+    await expect(
+      installForum(scenario, carolPending, properties, encode(forgedInvitation)),
+    ).rejects.toThrow(/The inviter is not allowed to invite agents to this forum/);
+  });
+});
@@ -0,0 +1,62 @@
+import { assert, test } from "vitest";
+
+import { runScenario, dhtSync, CallableCell } from '@holochain/tryorama';
+import { NewEntryAction, ActionHash, Record, Link, AppBundleSource, fakeDnaHash, fakeActionHash, fakeAgentPubKey, fakeEntryHash, encodeHashToBase64 } from '@holochain/client';
+import { decode } from '@msgpack/msgpack';
+
+import { createPost, samplePost, createComment, sampleComment } from './common.js';
+
+test('mention an agent in a Post and a Comment', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/forum.happ';
+
+    // Set up the app to be installed 
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    const bobMention = `@${encodeHashToBase64(bob.agentPubKey)}`;
+
+    // Alice mentions Bob in a Post, then in a Comment on it
+    const postRecord: Record = await createPost(
+      alice.cells[0],
+      await samplePost(alice.cells[0], { content: `What do you think, ${bobMention}?` })
+    );
+    const postHash = postRecord.signed_action.hashed.hash;
+    const commentRecord: Record = await createComment(
+      alice.cells[0],
+      await sampleComment(alice.cells[0], {
+        post_hash: postHash,
+        comment: `${bobMention} ping`,
+      })
+    );
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob finds both mentions, newest first
+    const links: Link[] = await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_my_mentions",
+      payload: null,
+    });
+    assert.equal(links.length, 2);
+    assert.deepEqual(links[0].target, commentRecord.signed_action.hashed.hash);
+    assert.deepEqual(links[1].target, postHash);
+
+    // Alice wasn't mentioned
+    const aliceLinks: Link[] = await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_my_mentions",
+      payload: null,
+    });
+    assert.equal(aliceLinks.length, 0);
+  });
+});
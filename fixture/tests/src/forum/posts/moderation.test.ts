@@ -0,0 +1,186 @@
+import { assert, expect, test } from "vitest";
+
+import { runScenario, dhtSync, CallableCell } from '@holochain/tryorama';
+import { NewEntryAction, ActionHash, Record, Link, AppBundleSource, fakeDnaHash, fakeActionHash, fakeAgentPubKey, fakeEntryHash } from '@holochain/client';
+import { decode } from '@msgpack/msgpack';
+
+import { createPost, createComment, sampleComment } from './common.js';
+import { addPlayersWithProperties, agentKeyProperty } from '../common.js';
+
+test('report a Post and keep moderation to moderators', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/forum.happ';
+
+    // Set up the app to be installed 
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a Post with a Comment
+    const postHash = (await createPost(alice.cells[0])).signed_action.hashed.hash;
+    await createComment(alice.cells[0], await sampleComment(alice.cells[0], { post_hash: postHash }));
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob reports the Post
+    await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "report_content",
+      payload: { target_hash: postHash, reason: "Spam", details: "Buy now!" },
+    });
+
+    // The forum has no moderators, so Bob can neither see the queue nor hide the Post
+    await expect(bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_moderation_queue",
+      payload: null,
+    })).rejects.toThrow(/Only moderators can do this/);
+    await expect(bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "take_moderation_action",
+      payload: { target_hash: postHash, kind: "Hide", reason: "", report_hash: null },
+    })).rejects.toThrow(/Only moderators can do this/);
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Nothing was hidden, with or without the filter
+    let posts: Link[] = await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_all_posts",
+      payload: { exclude_hidden: true },
+    });
+    assert.equal(posts.length, 1);
+    let comments: Link[] = await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_comments_for_post",
+      payload: { post_hash: postHash, exclude_hidden: true },
+    });
+    assert.equal(comments.length, 1);
+    comments = await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_comments_for_post",
+      payload: postHash,
+    });
+    assert.equal(comments.length, 1);
+  });
+});
+
+test('moderators hide reported content and resolve reports', async () => {
+  await runScenario(async scenario => {
+    // Alice moderates the forum
+    const [alice, bob] = await addPlayersWithProperties(scenario, 2, agents => ({
+      moderators: [agentKeyProperty(agents[0])],
+    }));
+
+    // Bob creates a Post with a Comment, and reports the Post twice
+    const postHash = (await createPost(bob.cells[0])).signed_action.hashed.hash;
+    const commentHash = (await createComment(bob.cells[0], await sampleComment(bob.cells[0], { post_hash: postHash }))).signed_action.hashed.hash;
+    const reportHash = (await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "report_content",
+      payload: { target_hash: postHash, reason: "Spam", details: "Buy now!" },
+    })).signed_action.hashed.hash;
+    const secondReportHash = (await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "report_content",
+      payload: { target_hash: postHash, reason: "OffTopic", details: "" },
+    })).signed_action.hashed.hash;
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Alice sees both reports in the queue
+    let queue: Link[] = await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_moderation_queue",
+      payload: null,
+    });
+    assert.equal(queue.length, 2);
+
+    // Alice hides the Comment, and resolves the first report by hiding the Post
+    await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "take_moderation_action",
+      payload: { target_hash: commentHash, kind: "Hide", reason: "Off topic", report_hash: null },
+    });
+    const moderationAction: Record = await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "resolve_report",
+      payload: { report_hash: reportHash, action: "Hide", reason: "Spam" },
+    });
+    assert.ok(moderationAction);
+
+    // And dismisses the second one
+    const dismissed = await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "resolve_report",
+      payload: { report_hash: secondReportHash, action: null, reason: "Duplicate" },
+    });
+    assert.equal(dismissed, null);
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // The queue is empty
+    queue = await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_moderation_queue",
+      payload: null,
+    });
+    assert.equal(queue.length, 0);
+
+    // The hidden Post and Comment are left out of the filtered listings only
+    let posts: Link[] = await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_all_posts",
+      payload: { exclude_hidden: true },
+    });
+    assert.equal(posts.length, 0);
+    posts = await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_all_posts",
+      payload: null,
+    });
+    assert.equal(posts.length, 1);
+    let comments: Link[] = await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_comments_for_post",
+      payload: { post_hash: postHash, exclude_hidden: true },
+    });
+    assert.equal(comments.length, 0);
+    comments = await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_comments_for_post",
+      payload: postHash,
+    });
+    assert.equal(comments.length, 1);
+
+    // Alice restores the Post, which is listed again
+    await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "take_moderation_action",
+      payload: { target_hash: postHash, kind: "Restore", reason: "Appeal accepted", report_hash: null },
+    });
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    posts = await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_all_posts",
+      payload: { exclude_hidden: true },
+    });
+    assert.equal(posts.length, 1);
+    const actions: Link[] = await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_moderation_actions_for_target",
+      payload: postHash,
+    });
+    assert.equal(actions.length, 2);
+  });
+});
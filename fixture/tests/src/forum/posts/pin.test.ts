@@ -0,0 +1,106 @@
+import { assert, expect, test } from "vitest";
+
+import { runScenario, dhtSync, CallableCell } from '@holochain/tryorama';
+import { NewEntryAction, ActionHash, Record, AppBundleSource, fakeDnaHash, fakeActionHash, fakeAgentPubKey, fakeEntryHash } from '@holochain/client';
+import { decode } from '@msgpack/msgpack';
+
+import { createPost, samplePost } from './common.js';
+import { addPlayersWithProperties, agentKeyProperty } from '../common.js';
+
+test('only moderators can pin Posts', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/forum.happ';
+
+    // Set up the app to be installed 
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a Post
+    const postHash = (await createPost(alice.cells[0])).signed_action.hashed.hash;
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // The forum has no moderators, so not even the author can pin it
+    await expect(alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "pin_post",
+      payload: postHash,
+    })).rejects.toThrow(/Only moderators can pin posts/);
+
+    const pinnedPosts: Record[] = await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_pinned_posts",
+      payload: null,
+    });
+    assert.equal(pinnedPosts.length, 0);
+  });
+});
+
+test('moderators pin and unpin Posts, which are listed at their latest revision', async () => {
+  await runScenario(async scenario => {
+    // Alice moderates the forum
+    const [alice, bob] = await addPlayersWithProperties(scenario, 2, agents => ({
+      moderators: [agentKeyProperty(agents[0])],
+    }));
+
+    // Bob creates a Post and Alice pins it
+    const postHash = (await createPost(bob.cells[0])).signed_action.hashed.hash;
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "pin_post",
+      payload: postHash,
+    });
+
+    // Bob updates the Post after it was pinned
+    const updatedPost = await samplePost(bob.cells[0], { title: "Updated after pinning" });
+    const updatedRecord: Record = await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "update_post",
+      payload: {
+        original_post_hash: postHash,
+        previous_post_hash: postHash,
+        updated_post: updatedPost,
+      },
+    });
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // The pinned Posts show the latest revision
+    let pinnedPosts: Record[] = await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_pinned_posts",
+      payload: null,
+    });
+    assert.equal(pinnedPosts.length, 1);
+    assert.deepEqual(pinnedPosts[0].signed_action.hashed.hash, updatedRecord.signed_action.hashed.hash);
+    assert.deepEqual(decode((pinnedPosts[0].entry as any).Present.entry) as any, updatedPost);
+
+    // Alice unpins it
+    await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "unpin_post",
+      payload: postHash,
+    });
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    pinnedPosts = await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_pinned_posts",
+      payload: null,
+    });
+    assert.equal(pinnedPosts.length, 0);
+  });
+});
@@ -0,0 +1,83 @@
+import { assert, expect, test } from "vitest";
+
+import { runScenario, dhtSync, CallableCell } from '@holochain/tryorama';
+import { NewEntryAction, ActionHash, Record, AppBundleSource, fakeDnaHash, fakeActionHash, fakeAgentPubKey, fakeEntryHash } from '@holochain/client';
+import { decode } from '@msgpack/msgpack';
+
+import { createPost } from './common.js';
+
+test('vote on a Poll and tally the results', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/forum.happ';
+
+    // Set up the app to be installed 
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice adds a single choice Poll closing in an hour to their Post
+    const postHash = (await createPost(alice.cells[0])).signed_action.hashed.hash;
+    const pollRecord: Record = await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "create_poll",
+      payload: {
+        post_hash: postHash,
+        question: "Where should we meet?",
+        options: ["Office", "Online", "Park"],
+        closes_at: (Date.now() + 60 * 60 * 1000) * 1000,
+        multiple_choice: false,
+      },
+    });
+    const pollHash = pollRecord.signed_action.hashed.hash;
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob can't pick two options, nor one that doesn't exist
+    await expect(bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "vote_on_poll",
+      payload: { poll_hash: pollHash, option_indices: [0, 1] },
+    })).rejects.toThrow(/single choice/);
+    await expect(bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "vote_on_poll",
+      payload: { poll_hash: pollHash, option_indices: [3] },
+    })).rejects.toThrow(/no option number 3/);
+
+    // Both vote once, and Bob can't vote again
+    await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "vote_on_poll",
+      payload: { poll_hash: pollHash, option_indices: [1] },
+    });
+    await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "vote_on_poll",
+      payload: { poll_hash: pollHash, option_indices: [1] },
+    });
+    await expect(bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "vote_on_poll",
+      payload: { poll_hash: pollHash, option_indices: [2] },
+    })).rejects.toThrow(/only vote once/);
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    const results: any = await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_poll_results",
+      payload: pollHash,
+    });
+    assert.deepEqual(results.counts, [0, 2, 0]);
+    assert.equal(results.voters, 2);
+    assert.isFalse(results.closed);
+  });
+});
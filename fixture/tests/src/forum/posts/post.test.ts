@@ -1,10 +1,11 @@
-import { assert, test } from "vitest";
+import { assert, expect, test } from "vitest";
 
 import { runScenario, dhtSync, CallableCell } from '@holochain/tryorama';
-import { NewEntryAction, ActionHash, Record, AppBundleSource, fakeDnaHash, fakeActionHash, fakeAgentPubKey, fakeEntryHash } from '@holochain/client';
+import { NewEntryAction, ActionHash, Record, Link, AppBundleSource, fakeDnaHash, fakeActionHash, fakeAgentPubKey, fakeEntryHash } from '@holochain/client';
 import { decode } from '@msgpack/msgpack';
 
-import { createPost, samplePost } from './common.js';
+import { createPost, samplePost, createComment, sampleComment, createRawLink } from './common.js';
+import { addPlayersWithProperties, agentKeyProperty } from '../common.js';
 
 test('create Post', async () => {
   await runScenario(async scenario => {
@@ -195,7 +196,8 @@ test('create and delete Post', async () => {
       fn_name: "get_oldest_delete_for_post",
       payload: record.signed_action.hashed.hash,
     });
-    assert.ok(oldestDeleteForPost);
+    assert.ok(oldestDeleteForPost.delete);
+    assert.equal(oldestDeleteForPost.restored_as, null);
         
     // Bob gets the deletions for Post
     const deletesForPost = await bob.cells[0].callZome({
@@ -208,3 +210,441 @@ test('create and delete Post', async () => {
 
   });
 });
+
+test('only the author can update a Post', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/forum.happ';
+
+    // Set up the app to be installed 
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a Post
+    const record: Record = await createPost(alice.cells[0]);
+    const originalActionHash = record.signed_action.hashed.hash;
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob tries to update Alice's Post
+    const updateInput = {
+      original_post_hash: originalActionHash,
+      previous_post_hash: originalActionHash,
+      updated_post: await samplePost(bob.cells[0], { title: "Not yours" }),
+    };
+    await expect(bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "update_post",
+      payload: updateInput,
+    })).rejects.toThrow(/Only the author of a Post can update it/);
+  });
+});
+
+test('only revisions of a Post can be linked as its updates', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/forum.happ';
+
+    // Set up the app to be installed 
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates two unrelated Posts
+    const firstPostHash = (await createPost(alice.cells[0])).signed_action.hashed.hash;
+    const secondPostHash = (await createPost(alice.cells[0])).signed_action.hashed.hash;
+
+    // Updating the second Post can't be linked as a revision of the first one
+    await expect(alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "update_post",
+      payload: {
+        original_post_hash: firstPostHash,
+        previous_post_hash: secondPostHash,
+        updated_post: await samplePost(alice.cells[0], { title: "Not a revision" }),
+      },
+    })).rejects.toThrow(/The updated Post is not a revision of the base Post/);
+
+    // Nor can the second Post itself
+    await expect(
+      createRawLink(alice.cells[0], firstPostHash, secondPostHash, "PostUpdates"),
+    ).rejects.toThrow(/PostUpdates links must point to an Update of the Post/);
+
+    // Alice updates the first Post
+    const updatedRecord: Record = await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "update_post",
+      payload: {
+        original_post_hash: firstPostHash,
+        previous_post_hash: firstPostHash,
+        updated_post: await samplePost(alice.cells[0], { title: "A real revision" }),
+      },
+    });
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob can't link Alice's update as a revision of the first Post again
+    await expect(
+      createRawLink(bob.cells[0], firstPostHash, updatedRecord.signed_action.hashed.hash, "PostUpdates"),
+    ).rejects.toThrow(/Only the author of an update can link it as a revision/);
+  });
+});
+
+test('only the author or a moderator can delete a Post', async () => {
+  await runScenario(async scenario => {
+    // Alice moderates the forum and Bob created it
+    const [alice, bob, carol, dave, erin] = await addPlayersWithProperties(scenario, 5, agents => ({
+      moderators: [agentKeyProperty(agents[0])],
+      progenitor: agentKeyProperty(agents[1]),
+    }));
+    const players = [alice, bob, carol, dave, erin];
+
+    // Carol creates two Posts
+    const firstPostHash = (await createPost(carol.cells[0])).signed_action.hashed.hash;
+    const secondPostHash = (await createPost(carol.cells[0])).signed_action.hashed.hash;
+
+    await dhtSync(players, alice.cells[0].cell_id[0]);
+
+    // Dave is not a moderator, so they can't delete Carol's Post
+    await expect(dave.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "delete_post",
+      payload: firstPostHash,
+    })).rejects.toThrow(/Only the author of a Post or a moderator can delete it/);
+
+    // Alice moderates the forum and Bob is its progenitor, so each of them can
+    await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "delete_post",
+      payload: firstPostHash,
+    });
+    await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "delete_post",
+      payload: secondPostHash,
+    });
+  });
+});
+
+test('only the authors involved or a moderator can delete a Comment and its link', async () => {
+  await runScenario(async scenario => {
+    // Alice moderates the forum
+    const [alice, carol, dave, erin] = await addPlayersWithProperties(scenario, 4, agents => ({
+      moderators: [agentKeyProperty(agents[0])],
+    }));
+    const players = [alice, carol, dave, erin];
+
+    // Dave comments twice on Carol's Post
+    const postHash = (await createPost(carol.cells[0])).signed_action.hashed.hash;
+    const firstCommentHash = (await createComment(
+      dave.cells[0],
+      await sampleComment(dave.cells[0], { post_hash: postHash })
+    )).signed_action.hashed.hash;
+    const secondCommentHash = (await createComment(
+      dave.cells[0],
+      await sampleComment(dave.cells[0], { post_hash: postHash })
+    )).signed_action.hashed.hash;
+
+    await dhtSync(players, alice.cells[0].cell_id[0]);
+
+    // Erin wrote neither the Post nor the Comments, so they can't unlink a Comment from the Post
+    const links: Link[] = await erin.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_comments_for_post",
+      payload: postHash,
+    });
+    assert.equal(links.length, 2);
+    const firstCommentLink = links.find(link => link.target.toString() === firstCommentHash.toString());
+    await expect(erin.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "delete_raw_link",
+      payload: firstCommentLink!.create_link_hash,
+    })).rejects.toThrow(/Only the author of a PostToComments link, of its Comment, of its Post or a moderator can delete it/);
+
+    // Carol wrote the Post, so they can
+    await carol.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "delete_raw_link",
+      payload: firstCommentLink!.create_link_hash,
+    });
+
+    await dhtSync(players, alice.cells[0].cell_id[0]);
+
+    // Nor can Erin delete the unlinked Comment
+    await expect(erin.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "delete_comment",
+      payload: firstCommentHash,
+    })).rejects.toThrow(/Only the author of a Comment, the author of its Post or a moderator can delete it/);
+
+    // Carol wrote the Post and Alice moderates the forum, so each of them can delete a Comment
+    await carol.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "delete_comment",
+      payload: firstCommentHash,
+    });
+    await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "delete_comment",
+      payload: secondCommentHash,
+    });
+
+    await dhtSync(players, alice.cells[0].cell_id[0]);
+
+    const remainingLinks: Link[] = await erin.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_comments_for_post",
+      payload: postHash,
+    });
+    assert.equal(remainingLinks.length, 0);
+  });
+});
+
+test('create Post with invalid content', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/forum.happ';
+
+    // Set up the app to be installed 
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice can't create a Post with a blank title
+    await expect(
+      createPost(alice.cells[0], await samplePost(alice.cells[0], { title: "   " }))
+    ).rejects.toThrow(/Post title cannot be empty/);
+
+    // Alice can't create a Post with content beyond the default limit
+    await expect(
+      createPost(alice.cells[0], await samplePost(alice.cells[0], { content: "a".repeat(10_001) }))
+    ).rejects.toThrow(/Post content cannot be longer than/);
+  });
+});
+
+test('the DNA properties change the limits of a forum', async () => {
+  await runScenario(async scenario => {
+    const [alice] = await addPlayersWithProperties(scenario, 1, () => ({
+      max_title_length: 10,
+      max_content_length: 20_000,
+    }));
+
+    // Alice can't create a Post with a title beyond the configured limit
+    await expect(
+      createPost(alice.cells[0], await samplePost(alice.cells[0], { title: "a".repeat(11) }))
+    ).rejects.toThrow(/Post title cannot be longer than 10 characters/);
+
+    // But can go beyond the default content limit
+    await createPost(alice.cells[0], await samplePost(alice.cells[0], {
+      title: "a".repeat(10),
+      content: "a".repeat(10_001),
+    }));
+  });
+});
+
+test('actions dated right after what they reference are accepted and read back', async () => {
+  await runScenario(async scenario => {
+    // No future skew is tolerated, so reads only keep what is dated up to the reader's clock
+    const [alice] = await addPlayersWithProperties(scenario, 1, () => ({
+      max_future_skew_seconds: 0,
+    }));
+
+    // Alice creates a Post, then immediately comments on it and updates it, so each action is
+    // dated at or just after the one it references
+    const postHash = (await createPost(alice.cells[0])).signed_action.hashed.hash;
+    const commentHash = (await createComment(alice.cells[0], {
+      comment: "Lorem ipsum dolor sit amet, consectetur adipiscing elit.",
+      post_hash: postHash,
+      parent_comment_hash: null,
+    })).signed_action.hashed.hash;
+    const updatedPost = await samplePost(alice.cells[0], { title: "Updated right away" });
+    const updatedRecord: Record = await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "update_post",
+      payload: {
+        original_post_hash: postHash,
+        previous_post_hash: postHash,
+        updated_post: updatedPost,
+      },
+    });
+
+    // The latest revision and the Comment are read back
+    const latestRecord: Record = await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_latest_post",
+      payload: postHash,
+    });
+    assert.deepEqual(latestRecord.signed_action.hashed.hash, updatedRecord.signed_action.hashed.hash);
+    const links: Link[] = await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_comments_for_post",
+      payload: postHash,
+    });
+    assert.equal(links.length, 1);
+    assert.deepEqual(links[0].target, commentHash);
+  });
+});
+
+test('update a Post with edit summaries and read its history', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/forum.happ';
+
+    // Set up the app to be installed 
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a Post and edits it twice, once with a summary
+    const originalPostHash = (await createPost(alice.cells[0])).signed_action.hashed.hash;
+    const firstRevision: Record = await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "update_post",
+      payload: {
+        original_post_hash: originalPostHash,
+        previous_post_hash: originalPostHash,
+        updated_post: await samplePost(alice.cells[0], { title: "Fixed title" }),
+        edit_summary: "Fix typo in title",
+      },
+    });
+    const secondRevision: Record = await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "update_post",
+      payload: {
+        original_post_hash: originalPostHash,
+        previous_post_hash: firstRevision.signed_action.hashed.hash,
+        updated_post: await samplePost(alice.cells[0], { title: "Fixed title again" }),
+      },
+    });
+
+    // Edit summaries have a length limit
+    await expect(alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "update_post",
+      payload: {
+        original_post_hash: originalPostHash,
+        previous_post_hash: secondRevision.signed_action.hashed.hash,
+        updated_post: await samplePost(alice.cells[0]),
+        edit_summary: "a".repeat(201),
+      },
+    })).rejects.toThrow(/Edit summaries cannot be longer than/);
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    const history: any[] = await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_post_history",
+      payload: originalPostHash,
+    });
+    assert.equal(history.length, 3);
+    assert.deepEqual(history[0].action_hash, originalPostHash);
+    assert.equal(history[0].edit_summary, null);
+    assert.deepEqual(history[1].action_hash, firstRevision.signed_action.hashed.hash);
+    assert.equal(history[1].edit_summary, "Fix typo in title");
+    assert.deepEqual(history[2].action_hash, secondRevision.signed_action.hashed.hash);
+    assert.equal(history[2].edit_summary, null);
+    assert.deepEqual(history[2].author, alice.agentPubKey);
+  });
+});
+
+test('delete a Post along with its Comments', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/forum.happ';
+
+    // Set up the app to be installed 
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a Post
+    const postHash = (await createPost(alice.cells[0])).signed_action.hashed.hash;
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob comments on it
+    const commentHash = (await createComment(
+      bob.cells[0],
+      await sampleComment(bob.cells[0], { post_hash: postHash }),
+    )).signed_action.hashed.hash;
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob is neither the author of the Post nor a moderator, so they are turned away before
+    // their own Comment is deleted
+    await expect(bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "delete_post_with_comments",
+      payload: { original_post_hash: postHash, delete_comments: true },
+    })).rejects.toThrow(/Only the author of a Post or a moderator can delete it along with its comments/);
+    const commentLinks: Link[] = await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_comments_for_post",
+      payload: postHash,
+    });
+    assert.equal(commentLinks.length, 1);
+
+    // Alice deletes their Post along with Bob's Comment
+    const report: any = await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "delete_post_with_comments",
+      payload: { original_post_hash: postHash, delete_comments: true },
+    });
+    assert.ok(report.delete_action_hash);
+    assert.equal(report.removed_comment_links.length, 1);
+    assert.deepEqual(report.deleted_comments, [commentHash]);
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob no longer reaches the Comment from the Post
+    const links: Link[] = await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_comments_for_post",
+      payload: postHash,
+    });
+    assert.equal(links.length, 0);
+
+    const oldestDeleteForComment = await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_oldest_delete_for_comment",
+      payload: commentHash,
+    });
+    assert.ok(oldestDeleteForComment);
+  });
+});
@@ -0,0 +1,46 @@
+import { assert, expect, test } from "vitest";
+
+import { runScenario, dhtSync, CallableCell } from '@holochain/tryorama';
+import { NewEntryAction, ActionHash, Record, AppBundleSource, fakeDnaHash, fakeActionHash, fakeAgentPubKey, fakeEntryHash } from '@holochain/client';
+import { decode } from '@msgpack/msgpack';
+
+import { createPost, createComment } from './common.js';
+import { addPlayersWithProperties } from '../common.js';
+
+test('forums without a rate limit accept bursts of Posts', async () => {
+  await runScenario(async scenario => {
+    const [alice] = await addPlayersWithProperties(scenario, 1, () => ({}));
+
+    // Rate limiting is opt-in, so Alice can create more than a rate-limited forum would allow
+    for (let i = 0; i < 6; i++) {
+      await createPost(alice.cells[0]);
+    }
+  });
+});
+
+test('the rate limit rejects the Post and Comment past the limit', async () => {
+  await runScenario(async scenario => {
+    const [alice] = await addPlayersWithProperties(scenario, 1, () => ({
+      rate_limit: { window_seconds: 60, max_posts: 2, max_comments: 2 },
+    }));
+
+    // Alice creates as many Posts as the window allows
+    const postHash = (await createPost(alice.cells[0])).signed_action.hashed.hash;
+    await createPost(alice.cells[0]);
+
+    // The next one is rejected
+    await expect(createPost(alice.cells[0])).rejects.toThrow(/Cannot create more than 2 posts every 60 seconds/);
+
+    // The same goes for Comments
+    const comment = {
+      comment: "Lorem ipsum dolor sit amet, consectetur adipiscing elit.",
+      post_hash: postHash,
+      parent_comment_hash: null,
+    };
+    for (let i = 0; i < 2; i++) {
+      await createComment(alice.cells[0], comment);
+    }
+    await expect(createComment(alice.cells[0], comment))
+      .rejects.toThrow(/Cannot create more than 2 comments every 60 seconds/);
+  });
+});
@@ -0,0 +1,72 @@
+import { assert, expect, test } from "vitest";
+
+import { runScenario, dhtSync, CallableCell } from '@holochain/tryorama';
+import { NewEntryAction, ActionHash, Record, AppBundleSource, fakeDnaHash, fakeActionHash, fakeAgentPubKey, fakeEntryHash } from '@holochain/client';
+import { decode } from '@msgpack/msgpack';
+
+import { createPost } from './common.js';
+
+test('react to a Post and get the reactions summary', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/forum.happ';
+
+    // Set up the app to be installed 
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a Post
+    const postHash = (await createPost(alice.cells[0])).signed_action.hashed.hash;
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob reacts to the Post
+    const reaction = { code: "heart", target_hash: postHash };
+    await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "add_reaction",
+      payload: reaction,
+    });
+
+    // Bob can't react twice to the same Post
+    await expect(bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "add_reaction",
+      payload: { ...reaction, code: "laugh" },
+    })).rejects.toThrow();
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Alice gets the reactions summary for the Post
+    let summary = await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_reactions_summary",
+      payload: postHash,
+    });
+    assert.deepEqual(summary, { counts: { heart: 1 }, my_reaction: null });
+
+    // Bob removes the reaction
+    await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "remove_reaction",
+      payload: postHash,
+    });
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    summary = await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_reactions_summary",
+      payload: postHash,
+    });
+    assert.deepEqual(summary, { counts: {}, my_reaction: null });
+  });
+});
@@ -0,0 +1,327 @@
+import { assert, expect, test } from "vitest";
+
+import { runScenario, dhtSync, CallableCell } from '@holochain/tryorama';
+import { NewEntryAction, ActionHash, Record, Link, AppBundleSource, fakeDnaHash, fakeActionHash, fakeAgentPubKey, fakeEntryHash } from '@holochain/client';
+import { decode } from '@msgpack/msgpack';
+
+import { createPost, createComment, sampleComment, samplePost, createRawLink } from './common.js';
+
+test('restore a deleted Post and list it again', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/forum.happ';
+
+    // Set up the app to be installed 
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a Post and deletes it
+    const record: Record = await createPost(alice.cells[0]);
+    const originalPostHash = record.signed_action.hashed.hash;
+    await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "delete_post",
+      payload: originalPostHash,
+    });
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob can't restore Alice's Post
+    await expect(bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "restore_post",
+      payload: originalPostHash,
+    })).rejects.toThrow(/Only the original author or a moderator can restore deleted content/);
+
+    // Alice restores it
+    const restoredRecord: Record = await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "restore_post",
+      payload: originalPostHash,
+    });
+    assert.deepEqual(decode((restoredRecord.entry as any).Present.entry), decode((record.entry as any).Present.entry));
+    const restoredPostHash = restoredRecord.signed_action.hashed.hash;
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob sees the restored copy in all_posts
+    const links: Link[] = await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_all_posts",
+      payload: null,
+    });
+    assert.equal(links.length, 1);
+    assert.deepEqual(links[0].target, restoredPostHash);
+
+    // And the delete of the original reports the copy it was restored as
+    const status: any = await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_oldest_delete_for_post",
+      payload: originalPostHash,
+    });
+    assert.ok(status.delete);
+    assert.deepEqual(status.restored_as, restoredPostHash);
+
+    // A Post can only be restored once
+    await expect(alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "restore_post",
+      payload: originalPostHash,
+    })).rejects.toThrow(/already been restored/);
+  });
+});
+
+test('restore a deleted Comment', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/forum.happ';
+
+    // Set up the app to be installed 
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a Comment on their Post
+    const sample = await sampleComment(alice.cells[0]);
+    const record: Record = await createComment(alice.cells[0], sample);
+    const originalCommentHash = record.signed_action.hashed.hash;
+
+    // Restoring a Comment that was never deleted fails
+    await expect(alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "restore_comment",
+      payload: originalCommentHash,
+    })).rejects.toThrow(/Only deleted content can be restored/);
+
+    await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "delete_comment",
+      payload: originalCommentHash,
+    });
+    const restoredRecord: Record = await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "restore_comment",
+      payload: originalCommentHash,
+    });
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob sees the restored copy under the Post
+    const links: Link[] = await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_comments_for_post",
+      payload: sample.post_hash,
+    });
+    assert.equal(links.length, 1);
+    assert.deepEqual(links[0].target, restoredRecord.signed_action.hashed.hash);
+
+    const status: any = await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_oldest_delete_for_comment",
+      payload: originalCommentHash,
+    });
+    assert.deepEqual(status.restored_as, restoredRecord.signed_action.hashed.hash);
+  });
+});
+
+test('a restored Post is its latest revision and keeps its comments', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/forum.happ';
+
+    // Set up the app to be installed 
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a Post and Bob comments on it
+    const record: Record = await createPost(alice.cells[0]);
+    const originalPostHash = record.signed_action.hashed.hash;
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    const commentRecord: Record = await createComment(bob.cells[0], {
+      comment: "Lorem ipsum dolor sit amet, consectetur adipiscing elit.",
+      post_hash: originalPostHash,
+      parent_comment_hash: null,
+    });
+    const commentHash = commentRecord.signed_action.hashed.hash;
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Alice edits the Post, then deletes it and restores it
+    const updatedPost = await samplePost(alice.cells[0], { title: "Edited title" });
+    await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "update_post",
+      payload: {
+        original_post_hash: originalPostHash,
+        previous_post_hash: originalPostHash,
+        updated_post: updatedPost,
+      },
+    });
+    await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "delete_post",
+      payload: originalPostHash,
+    });
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    const restoredRecord: Record = await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "restore_post",
+      payload: originalPostHash,
+    });
+    assert.deepEqual(decode((restoredRecord.entry as any).Present.entry) as any, updatedPost);
+    const restoredPostHash = restoredRecord.signed_action.hashed.hash;
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob's Comment is listed under the restored copy
+    let links: Link[] = await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_comments_for_post",
+      payload: restoredPostHash,
+    });
+    assert.equal(links.length, 1);
+    assert.deepEqual(links[0].target, commentHash);
+
+    // Bob deletes their Comment, which unlists it from the restored copy too
+    await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "delete_comment",
+      payload: commentHash,
+    });
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    links = await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_comments_for_post",
+      payload: restoredPostHash,
+    });
+    assert.equal(links.length, 0);
+  });
+});
+
+test('a restored Comment keeps its replies', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/forum.happ';
+
+    // Set up the app to be installed 
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice comments on their Post and Bob replies
+    const sample = await sampleComment(alice.cells[0]);
+    const commentRecord: Record = await createComment(alice.cells[0], sample);
+    const originalCommentHash = commentRecord.signed_action.hashed.hash;
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    const replyRecord: Record = await createComment(bob.cells[0], {
+      ...sample,
+      parent_comment_hash: originalCommentHash,
+    });
+    const replyHash = replyRecord.signed_action.hashed.hash;
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Alice deletes their Comment and restores it
+    await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "delete_comment",
+      payload: originalCommentHash,
+    });
+    const restoredRecord: Record = await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "restore_comment",
+      payload: originalCommentHash,
+    });
+    const restoredCommentHash = restoredRecord.signed_action.hashed.hash;
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob's reply is linked to the restored copy
+    const links: Link[] = await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_replies_for_comment",
+      payload: restoredCommentHash,
+    });
+    assert.equal(links.length, 1);
+    assert.deepEqual(links[0].target, replyHash);
+
+    // And the comment tree shows it under the restored copy
+    const tree: any[] = await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_comment_tree",
+      payload: sample.post_hash,
+    });
+    assert.equal(tree.length, 1);
+    assert.deepEqual(tree[0].record.signed_action.hashed.hash, restoredCommentHash);
+    assert.equal(tree[0].replies.length, 1);
+    assert.deepEqual(tree[0].replies[0].record.signed_action.hashed.hash, replyHash);
+  });
+});
+
+test('a Post can only be restored as an identical copy', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/forum.happ';
+
+    // Set up the app to be installed 
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice] = await scenario.addPlayersWithApps([appSource]);
+
+    // Alice creates a Post and deletes it
+    const originalPostHash = (await createPost(alice.cells[0])).signed_action.hashed.hash;
+    await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "delete_post",
+      payload: originalPostHash,
+    });
+
+    // A different Post can't be linked as its restored copy
+    const otherPost = await samplePost(alice.cells[0], { title: "Another title" });
+    const otherPostHash = (await createPost(alice.cells[0], otherPost)).signed_action.hashed.hash;
+    await expect(
+      createRawLink(alice.cells[0], otherPostHash, originalPostHash, "RestoredFrom"),
+    ).rejects.toThrow(/A restored Post must be identical to the deleted one/);
+  });
+});
@@ -0,0 +1,110 @@
+import { assert, expect, test } from "vitest";
+
+import { runScenario, dhtSync, CallableCell } from '@holochain/tryorama';
+import { NewEntryAction, ActionHash, Record, Link, AppBundleSource, fakeDnaHash, fakeActionHash, fakeAgentPubKey, fakeEntryHash } from '@holochain/client';
+import { decode, encode } from '@msgpack/msgpack';
+
+import { createPost, samplePost, createRawLink, getPathHash } from './common.js';
+
+test('tag a Post and retag it on update', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/forum.happ';
+
+    // Set up the app to be installed 
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a tagged Post
+    const record: Record = await createPost(
+      alice.cells[0],
+      await samplePost(alice.cells[0], { tags: ["rust", "holochain"] })
+    );
+    const postHash = record.signed_action.hashed.hash;
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob gets all tags
+    const tags: string[] = await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_all_tags",
+      payload: null,
+    });
+    assert.sameMembers(tags, ["rust", "holochain"]);
+
+    // Alice replaces the "rust" tag with "wasm"
+    await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "update_post",
+      payload: {
+        original_post_hash: postHash,
+        previous_post_hash: postHash,
+        updated_post: await samplePost(alice.cells[0], { tags: ["holochain", "wasm"] }),
+      },
+    });
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob no longer finds the Post under "rust", but finds it under "wasm"
+    let links: Link[] = await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_posts_by_tag",
+      payload: "rust",
+    });
+    assert.equal(links.length, 0);
+    links = await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_posts_by_tag",
+      payload: "wasm",
+    });
+    assert.equal(links.length, 1);
+    assert.deepEqual(links[0].target, postHash);
+  });
+});
+
+// The tag of a TagPath link: the serialized path component, one little-endian u32 per character.
+function pathComponentTag(component: string): Uint8Array {
+  const bytes = new Uint8Array(component.length * 4);
+  const view = new DataView(bytes.buffer);
+  [...component].forEach((c, i) => view.setUint32(i * 4, c.codePointAt(0)!, true));
+  return encode(bytes);
+}
+
+test('TagPath links can only add well-formed tags under the tags path', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/forum.happ';
+
+    // Set up the app to be installed 
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add a player with the test app to the Scenario.
+    const [alice] = await scenario.addPlayersWithApps([appSource]);
+
+    const tagsHash = await getPathHash(alice.cells[0], "tags");
+    const badTagHash = await getPathHash(alice.cells[0], "tags.Bad_Tag");
+
+    // Malformed tags are rejected
+    await expect(createRawLink(alice.cells[0], tagsHash, badTagHash, "TagPath", pathComponentTag("Bad_Tag")))
+      .rejects.toThrow(/must only contain lowercase letters and digits/);
+
+    // So are tags outside of the tags path
+    const postHash = (await createPost(alice.cells[0])).signed_action.hashed.hash;
+    const tagHash = await getPathHash(alice.cells[0], "tags.rust");
+    await expect(createRawLink(alice.cells[0], postHash, tagHash, "TagPath", pathComponentTag("rust")))
+      .rejects.toThrow(/TagPath links must have the tags path as their base/);
+
+    // And links whose target isn't the path their tag names
+    await expect(createRawLink(alice.cells[0], tagsHash, badTagHash, "TagPath", pathComponentTag("rust")))
+      .rejects.toThrow(/TagPath links must point to the path named by their tag/);
+  });
+});
@@ -0,0 +1,107 @@
+import { assert, test } from "vitest";
+
+import { runScenario, dhtSync, CallableCell } from '@holochain/tryorama';
+import { NewEntryAction, ActionHash, Record, AppBundleSource, fakeDnaHash, fakeActionHash, fakeAgentPubKey, fakeEntryHash } from '@holochain/client';
+import { decode } from '@msgpack/msgpack';
+
+import { createPost } from './common.js';
+
+test('vote on Posts and get the top posts', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/forum.happ';
+
+    // Set up the app to be installed 
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates two Posts
+    const firstPostHash = (await createPost(alice.cells[0])).signed_action.hashed.hash;
+    const secondPostHash = (await createPost(alice.cells[0])).signed_action.hashed.hash;
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob downvotes the first Post and upvotes the second one
+    await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "cast_vote",
+      payload: { target_hash: firstPostHash, direction: "Down" },
+    });
+    await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "cast_vote",
+      payload: { target_hash: secondPostHash, direction: "Up" },
+    });
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Alice gets the score of the second Post
+    const score = await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_score",
+      payload: secondPostHash,
+    });
+    assert.deepEqual(score, { upvotes: 1, downvotes: 0 });
+
+    // Alice gets the top posts
+    const topPosts: any[] = await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_top_posts",
+      payload: null,
+    });
+    assert.equal(topPosts.length, 2);
+    assert.deepEqual(topPosts[0].post_hash, secondPostHash);
+    assert.deepEqual(topPosts[1].post_hash, firstPostHash);
+  });
+});
+
+test('voting again replaces the previous Vote instead of adding to the score', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/forum.happ';
+
+    // Set up the app to be installed 
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a Post
+    const postHash = (await createPost(alice.cells[0])).signed_action.hashed.hash;
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob upvotes it twice, then changes their mind and downvotes it
+    for (const direction of ["Up", "Up", "Down"]) {
+      await bob.cells[0].callZome({
+        zome_name: "posts",
+        fn_name: "cast_vote",
+        payload: { target_hash: postHash, direction },
+      });
+    }
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Only Bob's latest Vote counts
+    const score = await alice.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_score",
+      payload: postHash,
+    });
+    assert.deepEqual(score, { upvotes: 0, downvotes: 1 });
+  });
+});
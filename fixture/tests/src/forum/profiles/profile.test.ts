@@ -0,0 +1,101 @@
+import { assert, expect, test } from "vitest";
+
+import { runScenario, dhtSync, CallableCell } from '@holochain/tryorama';
+import { NewEntryAction, ActionHash, Record, Link, AppBundleSource, fakeDnaHash, fakeActionHash, fakeAgentPubKey, fakeEntryHash } from '@holochain/client';
+import { decode } from '@msgpack/msgpack';
+
+import { createProfile, sampleProfile } from './common.js';
+import { createPost } from '../posts/common.js';
+
+test('create and update a Profile', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/forum.happ';
+
+    // Set up the app to be installed 
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a Profile
+    await createProfile(alice.cells[0]);
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob can't take Alice's nickname, even with different case
+    await expect(
+      createProfile(bob.cells[0], await sampleProfile(bob.cells[0], { nickname: "Alice" }))
+    ).rejects.toThrow(/already taken/);
+
+    // Alice renames themselves
+    await alice.cells[0].callZome({
+      zome_name: "profiles",
+      fn_name: "update_profile",
+      payload: await sampleProfile(alice.cells[0], { nickname: "alice_wonder" }),
+    });
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob sees the new nickname, and finds it by prefix
+    const nickname: string | null = await bob.cells[0].callZome({
+      zome_name: "profiles",
+      fn_name: "get_agent_nickname",
+      payload: alice.agentPubKey,
+    });
+    assert.equal(nickname, "alice_wonder");
+    const links: Link[] = await bob.cells[0].callZome({
+      zome_name: "profiles",
+      fn_name: "search_profiles",
+      payload: "ALI",
+    });
+    assert.equal(links.length, 1);
+    assert.deepEqual(links[0].author, alice.agentPubKey);
+
+    // The old nickname is free again
+    await createProfile(bob.cells[0], await sampleProfile(bob.cells[0], { nickname: "alice" }));
+  });
+});
+
+test('list Posts with their authors\' nicknames', async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + '/../workdir/forum.happ';
+
+    // Set up the app to be installed 
+    const appSource = { appBundleSource: { path: testAppPath } };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice has a Profile and Bob doesn't, and both create a Post
+    await createProfile(alice.cells[0]);
+    await createPost(alice.cells[0]);
+    await createPost(bob.cells[0]);
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    const posts: any[] = await bob.cells[0].callZome({
+      zome_name: "posts",
+      fn_name: "get_all_posts_with_authors",
+      payload: null,
+    });
+    assert.equal(posts.length, 2);
+    const alicePost = posts.find(post => post.author.toString() === alice.agentPubKey.toString());
+    const bobPost = posts.find(post => post.author.toString() === bob.agentPubKey.toString());
+    assert.equal(alicePost.nickname, "alice");
+    assert.equal(bobPost.nickname, null);
+  });
+});
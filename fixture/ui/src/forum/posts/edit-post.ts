@@ -67,6 +67,7 @@ export class EditPost extends LitElement {
 
 	async updatePost() {
 		const post: Post = {
+			...this.currentPost,
 			title: this._title!,
 			content: this._content!,
 		};
@@ -33,9 +33,22 @@ export type PostsSignal = {
   type: 'LinkDeleted';
   action: SignedActionHashed<DeleteLink>;
   link_type: string;
+} | {
+  type: 'PostDeletedWithComments';
+  report: CascadeDeleteReport;
 };
 
 export type EntryTypes =
+ | ({ type: 'ModerationAction'; } & ModerationAction)
+ | ({ type: 'Report'; } & Report)
+ | ({ type: 'Bookmark'; } & Bookmark)
+ | ({ type: 'PollVote'; } & PollVote)
+ | ({ type: 'Poll'; } & Poll)
+ | ({ type: 'FileMetadata'; } & FileMetadata)
+ | ({ type: 'FileChunk'; } & FileChunk)
+ | ({ type: 'Channel'; } & Channel)
+ | ({ type: 'Vote'; } & Vote)
+ | ({ type: 'Reaction'; } & Reaction)
  | ({ type: 'Comment'; } & Comment)
  | ({  type: 'Post'; } & Post);
 
@@ -45,6 +58,10 @@ export interface Post {
   title: string;
 
   content: string;
+
+  tags?: Array<string>;
+
+  channel_hash?: ActionHash | null;
 }
 
 
@@ -55,6 +72,168 @@ export interface Comment {
   comment: string;
 
   post_hash: ActionHash;
+
+  parent_comment_hash?: ActionHash | null;
+}
+
+
+
+export interface Reaction { 
+  code: string;
+
+  target_hash: ActionHash;
+}
+
+
+
+export type VoteDirection = 'Up' | 'Down';
+
+export interface Vote { 
+  target_hash: ActionHash;
+
+  direction: VoteDirection;
 }
 
 
+
+export interface Channel { 
+  name: string;
+
+  description: string;
+
+  moderators: Array<AgentPubKey>;
+}
+
+
+
+export interface PostWithAuthor { 
+  post_hash: ActionHash;
+
+  author: AgentPubKey;
+
+  nickname: string | null;
+}
+
+
+
+export interface FileChunk { 
+  bytes: Uint8Array;
+}
+
+
+
+export interface FileMetadata { 
+  name: string;
+
+  mime_type: string;
+
+  size: number;
+
+  chunks: Array<EntryHash>;
+
+  content_hash: Uint8Array;
+}
+
+
+
+export interface Poll { 
+  post_hash: ActionHash;
+
+  question: string;
+
+  options: Array<string>;
+
+  closes_at: number;
+
+  multiple_choice: boolean;
+}
+
+
+
+export interface PollVote { 
+  poll_hash: ActionHash;
+
+  option_indices: Array<number>;
+}
+
+
+
+export interface Bookmark { 
+  target_hash: ActionHash;
+
+  folder: string | null;
+
+  note: string | null;
+}
+
+
+
+export type ReportReason = 'Spam' | 'Harassment' | 'OffTopic' | 'Illegal' | 'Other';
+
+export interface Report { 
+  target_hash: ActionHash;
+
+  reason: ReportReason;
+
+  details: string;
+}
+
+
+
+export type ModerationActionKind = 'Hide' | 'Restore' | 'Warn';
+
+export interface ModerationAction { 
+  target_hash: ActionHash;
+
+  kind: ModerationActionKind;
+
+  reason: string;
+
+  report_hash: ActionHash | null;
+}
+
+
+
+export interface ListingOptions { 
+  exclude_hidden?: boolean;
+}
+
+
+
+export interface PostRevision { 
+  action_hash: ActionHash;
+
+  author: AgentPubKey;
+
+  timestamp: number;
+
+  edit_summary: string | null;
+}
+
+
+
+export interface DeletionStatus { 
+  delete: SignedActionHashed<Delete>;
+
+  restored_as: ActionHash | null;
+}
+
+
+
+export interface DeletePostWithCommentsInput { 
+  original_post_hash: ActionHash;
+
+  delete_comments: boolean;
+}
+
+
+
+export interface CascadeDeleteReport { 
+  original_post_hash: ActionHash;
+
+  delete_action_hash: ActionHash;
+
+  removed_comment_links: Array<ActionHash>;
+
+  deleted_comments: Array<ActionHash>;
+}
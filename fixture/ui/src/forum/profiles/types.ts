@@ -0,0 +1,12 @@
+export type EntryTypes =
+ | ({ type: 'Profile'; } & Profile);
+
+
+
+export interface Profile { 
+  nickname: string;
+
+  avatar: string | null;
+
+  bio: string;
+}